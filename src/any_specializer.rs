@@ -0,0 +1,136 @@
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData};
+
+/// Specialized behavior runner (Type-erased -> Owned)
+///
+/// Dispatches on the runtime type of a `Box<dyn Any>`, falling back to a
+/// closure that receives the box back if no arm matches.
+#[derive(Debug)]
+pub struct AnySpecializer<U, F>(Box<dyn Any>, F, PhantomData<fn() -> U>, bool);
+
+impl<U, F> AnySpecializer<U, F>
+where
+    F: FnOnce(Box<dyn Any>) -> U,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(params: Box<dyn Any>, f: F) -> Self {
+        Self(params, f, PhantomData, false)
+    }
+
+    /// Create a new specializer for the concrete type behind a boxed trait
+    /// object, with a fallback function.
+    ///
+    /// See [`AnySpecializerRef`](crate::AnySpecializerRef::from_downcast) for
+    /// an example.
+    #[cfg(feature = "downcast-rs")]
+    #[inline(always)]
+    pub fn from_downcast<T>(params: Box<T>, f: F) -> Self
+    where
+        T: downcast_rs::Downcast + ?Sized,
+    {
+        Self::new(params.into_any(), f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializer;
+    ///
+    /// fn specialized<U>(ty: Box<dyn Any>) -> Option<U>
+    /// where
+    ///     U: 'static,
+    /// {
+    ///     AnySpecializer::new(ty, |_| None)
+    ///         .specialize(|int: i32| -> Option<i32> { Some(int * 2) })
+    ///         .specialize(|int: u8| -> Option<String> {
+    ///             Some(int.to_string())
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32>(Box::new(3i32)), Some(6));
+    /// assert_eq!(specialized::<String>(Box::new(3u8)), Some("3".into()));
+    /// assert_eq!(specialized::<i32>(Box::new(3u8)), None);
+    /// assert_eq!(specialized::<i32>(Box::new(())), None);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> AnySpecializer<U, impl FnOnce(Box<dyn Any>) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AnySpecializer(ty, fallback, PhantomData, armed) = self;
+        let f = move |any: Box<dyn Any>| -> U {
+            if crate::api::type_eq::<U, R>() {
+                match any.downcast::<P>() {
+                    Ok(param) => {
+                        crate::trace::taken::<P, P>();
+
+                        return crate::cast_identity::<R, U>(f(*param))
+                            .unwrap();
+                    }
+                    Err(any) => {
+                        crate::trace::missed::<dyn Any, P>(armed);
+
+                        return fallback(any);
+                    }
+                }
+            }
+
+            crate::trace::missed::<dyn Any, P>(armed);
+
+            fallback(any)
+        };
+
+        AnySpecializer(ty, f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializer;
+    ///
+    /// fn specialized(ty: Box<dyn Any>) -> String {
+    ///     let fallback = |_| "unknown".to_owned();
+    ///
+    ///     AnySpecializer::new(ty, fallback)
+    ///         .specialize_param(|int: i32| (int * 2).to_string())
+    ///         .specialize_param(|string: String| string)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(Box::new(3)), "6");
+    /// assert_eq!(
+    ///     specialized(Box::new("Hello world".to_string())),
+    ///     "Hello world",
+    /// );
+    /// assert_eq!(specialized(Box::new(())), "unknown");
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(P) -> U,
+    ) -> AnySpecializer<U, impl FnOnce(Box<dyn Any>) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) -> U {
+        crate::trace::fallback::<dyn Any>(self.3);
+
+        (self.1)(self.0)
+    }
+}
@@ -0,0 +1,137 @@
+use core::{any::Any, marker::PhantomData};
+
+/// Specialized behavior runner (Type-erased mutably borrowed -> Owned)
+///
+/// Dispatches on the runtime type behind a `&mut dyn Any`, falling back to a
+/// closure that receives the reference back if no arm matches.
+#[derive(Debug)]
+pub struct AnySpecializerMut<'a, U, F>(
+    &'a mut dyn Any,
+    F,
+    PhantomData<fn() -> U>,
+    bool,
+);
+
+impl<'a, U, F> AnySpecializerMut<'a, U, F>
+where
+    F: FnOnce(&'a mut dyn Any) -> U,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(params: &'a mut dyn Any, f: F) -> Self {
+        Self(params, f, PhantomData, false)
+    }
+
+    /// Create a new specializer for the concrete type behind a mutable trait
+    /// object, with a fallback function.
+    ///
+    /// See [`AnySpecializerRef`](crate::AnySpecializerRef::from_downcast) for
+    /// an example.
+    #[cfg(feature = "downcast-rs")]
+    #[inline(always)]
+    pub fn from_downcast<T>(params: &'a mut T, f: F) -> Self
+    where
+        T: downcast_rs::Downcast + ?Sized,
+    {
+        Self::new(params.as_any_mut(), f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializerMut;
+    ///
+    /// fn specialized<U>(ty: &mut dyn Any) -> Option<U>
+    /// where
+    ///     U: 'static,
+    /// {
+    ///     AnySpecializerMut::new(ty, |_| None)
+    ///         .specialize(|int: &mut i32| -> Option<()> {
+    ///             *int *= 2;
+    ///             Some(())
+    ///         })
+    ///         .specialize(|int: &mut u8| -> Option<String> {
+    ///             Some(int.to_string())
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// let mut value = 3i32;
+    ///
+    /// assert_eq!(specialized::<()>(&mut value), Some(()));
+    /// assert_eq!(value, 6);
+    /// assert_eq!(specialized::<String>(&mut 3u8), Some("3".into()));
+    /// assert_eq!(specialized::<i32>(&mut 3u8), None);
+    /// assert_eq!(specialized::<i32>(&mut ()), None);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(&'a mut P) -> R,
+    ) -> AnySpecializerMut<'a, U, impl FnOnce(&'a mut dyn Any) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AnySpecializerMut(ty, fallback, PhantomData, armed) = self;
+        let f = move |any: &'a mut dyn Any| -> U {
+            if crate::api::type_eq::<U, R>() && any.is::<P>() {
+                crate::trace::taken::<P, P>();
+                let param = any.downcast_mut::<P>().unwrap();
+
+                return crate::cast_identity::<R, U>(f(param)).unwrap();
+            }
+
+            crate::trace::missed::<dyn Any, P>(armed);
+
+            fallback(any)
+        };
+
+        AnySpecializerMut(ty, f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializerMut;
+    ///
+    /// fn specialized(ty: &mut dyn Any) -> String {
+    ///     let fallback = |_| "unknown".to_owned();
+    ///
+    ///     AnySpecializerMut::new(ty, fallback)
+    ///         .specialize_param(|int: &mut i32| (*int * 2).to_string())
+    ///         .specialize_param(|string: &mut String| string.split_off(5))
+    ///         .run()
+    /// }
+    ///
+    /// let mut string = "Hello world".to_string();
+    ///
+    /// assert_eq!(specialized(&mut 3), "6");
+    /// assert_eq!(specialized(&mut string), " world");
+    /// assert_eq!(string, "Hello");
+    /// assert_eq!(specialized(&mut ()), "unknown");
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(&'a mut P) -> U,
+    ) -> AnySpecializerMut<'a, U, impl FnOnce(&'a mut dyn Any) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) -> U {
+        crate::trace::fallback::<dyn Any>(self.3);
+
+        (self.1)(self.0)
+    }
+}
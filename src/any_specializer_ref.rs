@@ -0,0 +1,163 @@
+use core::{any::Any, marker::PhantomData};
+
+/// Specialized behavior runner (Type-erased borrowed -> Owned)
+///
+/// Dispatches on the runtime type behind a `&dyn Any`, falling back to a
+/// closure that receives the reference back if no arm matches.
+#[derive(Debug)]
+pub struct AnySpecializerRef<'a, U, F>(
+    &'a dyn Any,
+    F,
+    PhantomData<fn() -> U>,
+    bool,
+);
+
+impl<'a, U, F> AnySpecializerRef<'a, U, F>
+where
+    F: FnOnce(&'a dyn Any) -> U,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(params: &'a dyn Any, f: F) -> Self {
+        Self(params, f, PhantomData, false)
+    }
+
+    /// Create a new specializer for the concrete type behind a trait object,
+    /// with a fallback function.
+    ///
+    /// ```rust
+    /// use downcast_rs::{Downcast, impl_downcast};
+    /// use specializer::AnySpecializerRef;
+    ///
+    /// trait Shape: Downcast {
+    ///     fn area(&self) -> f64;
+    /// }
+    ///
+    /// impl_downcast!(Shape);
+    ///
+    /// struct Square(f64);
+    /// struct Circle(f64);
+    ///
+    /// impl Shape for Square {
+    ///     fn area(&self) -> f64 {
+    ///         self.0 * self.0
+    ///     }
+    /// }
+    ///
+    /// impl Shape for Circle {
+    ///     fn area(&self) -> f64 {
+    ///         3.0 * self.0 * self.0
+    ///     }
+    /// }
+    ///
+    /// fn describe(shape: &dyn Shape) -> String {
+    ///     AnySpecializerRef::from_downcast(shape, |_| {
+    ///         format!("shape of area {}", shape.area())
+    ///     })
+    ///     .specialize_param(|square: &Square| format!("square {}", square.0))
+    ///     .run()
+    /// }
+    ///
+    /// assert_eq!(describe(&Square(2.0)), "square 2");
+    /// assert_eq!(describe(&Circle(1.0)), "shape of area 3");
+    /// ```
+    #[cfg(feature = "downcast-rs")]
+    #[inline(always)]
+    pub fn from_downcast<T>(params: &'a T, f: F) -> Self
+    where
+        T: downcast_rs::Downcast + ?Sized,
+    {
+        Self::new(params.as_any(), f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializerRef;
+    ///
+    /// fn specialized<U>(ty: &dyn Any) -> Option<U>
+    /// where
+    ///     U: 'static,
+    /// {
+    ///     AnySpecializerRef::new(ty, |_| None)
+    ///         .specialize(|int: &i32| -> Option<i32> { Some(*int * 2) })
+    ///         .specialize(|int: &u8| -> Option<String> {
+    ///             Some(int.to_string())
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32>(&3i32), Some(6));
+    /// assert_eq!(specialized::<String>(&3u8), Some("3".into()));
+    /// assert_eq!(specialized::<i32>(&3u8), None);
+    /// assert_eq!(specialized::<i32>(&()), None);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(&'a P) -> R,
+    ) -> AnySpecializerRef<'a, U, impl FnOnce(&'a dyn Any) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AnySpecializerRef(ty, fallback, PhantomData, armed) = self;
+        let f = move |any: &'a dyn Any| -> U {
+            if crate::api::type_eq::<U, R>() {
+                if let Some(param) = any.downcast_ref::<P>() {
+                    crate::trace::taken::<P, P>();
+
+                    return crate::cast_identity::<R, U>(f(param)).unwrap();
+                }
+            }
+
+            crate::trace::missed::<dyn Any, P>(armed);
+
+            fallback(any)
+        };
+
+        AnySpecializerRef(ty, f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::AnySpecializerRef;
+    ///
+    /// fn specialized(ty: &dyn Any) -> String {
+    ///     let fallback = |_| "unknown".to_owned();
+    ///
+    ///     AnySpecializerRef::new(ty, fallback)
+    ///         .specialize_param(|int: &i32| (int * 2).to_string())
+    ///         .specialize_param(|string: &String| string.clone())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(&3), "6");
+    /// assert_eq!(specialized(&"Hello world".to_string()), "Hello world");
+    /// assert_eq!(specialized(&()), "unknown");
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(&'a P) -> U,
+    ) -> AnySpecializerRef<'a, U, impl FnOnce(&'a dyn Any) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) -> U {
+        crate::trace::fallback::<dyn Any>(self.3);
+
+        (self.1)(self.0)
+    }
+}
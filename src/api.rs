@@ -1,4 +1,4 @@
-use core::any::Any;
+use core::any::{Any, TypeId};
 
 use crate::CastIdentityBorrowed;
 
@@ -22,7 +22,7 @@ where
     T: 'static,
     U: 'static,
 {
-    <(dyn Any + 'static)>::downcast_mut::<Option<U>>(&mut Some(ty))?.take()
+    <dyn Any + 'static>::downcast_mut::<Option<U>>(&mut Some(ty))?.take()
 }
 
 /// Attempt to cast `&T` to `&U`.
@@ -42,13 +42,30 @@ where
 ///     Some("Hello"),
 /// );
 /// ```
+///
+/// `T` and `U` may be unsized:
+///
+/// ```rust
+/// fn only_str<T: ?Sized + 'static>(t: &T) -> Option<&str> {
+///     specializer::cast_identity_ref::<T, str>(t)
+/// }
+///
+/// assert_eq!(only_str("Hello"), Some("Hello"));
+/// assert!(only_str(&[1u8, 2][..]).is_none());
+/// ```
 #[inline(always)]
 pub fn cast_identity_ref<T, U>(ty: &T) -> Option<&U>
 where
-    T: 'static,
-    U: 'static,
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
 {
-    <(dyn Any + 'static)>::downcast_ref::<U>(ty)
+    // Function pointers that are generic over the lifetime are `'static` (and
+    // sized), so the identity function can be cast instead of the reference
+    let identity: for<'a> fn(&'a T) -> &'a T = |ty| ty;
+
+    Some(cast_identity::<_, for<'a> fn(&'a T) -> &'a U>(identity)?(
+        ty,
+    ))
 }
 
 /// Attempt to cast `&mut T` to `&mut U`.
@@ -71,10 +88,14 @@ where
 #[inline(always)]
 pub fn cast_identity_mut<T, U>(ty: &mut T) -> Option<&mut U>
 where
-    T: 'static,
-    U: 'static,
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
 {
-    <(dyn Any + 'static)>::downcast_mut::<U>(ty)
+    let identity: for<'a> fn(&'a mut T) -> &'a mut T = |ty| ty;
+
+    Some(cast_identity::<_, for<'a> fn(&'a mut T) -> &'a mut U>(
+        identity,
+    )?(ty))
 }
 
 /// Attempt to cast borrowed `T` to `U`.
@@ -111,3 +132,78 @@ where
 {
     T::is_same().then(|| T::cast_identity(ty)).flatten()
 }
+
+/// Cast borrowed `T` to `U` on a specialized arm, where the cast can't fail
+/// unless the [`CastIdentityBorrowed`] impl is inconsistent.
+///
+/// With the `strict` feature, an inconsistent impl panics with a message
+/// naming the offending type pair.
+#[inline(always)]
+pub(crate) fn cast_matched<T, U>(ty: T) -> U
+where
+    T: CastIdentityBorrowed<U>,
+{
+    #[cfg(feature = "strict")]
+    {
+        let (t, u) = (core::any::type_name::<T>(), core::any::type_name::<U>());
+
+        assert!(
+            T::is_same(),
+            "inconsistent `CastIdentityBorrowed<{u}>` impl for `{t}`: \
+             `is_same()` returned false for a matched arm",
+        );
+
+        let Some(u_ty) = T::cast_identity(ty) else {
+            panic!(
+                "inconsistent `CastIdentityBorrowed<{u}>` impl for `{t}`: \
+                 `is_same()` returned true, but `cast_identity()` failed",
+            );
+        };
+
+        u_ty
+    }
+
+    #[cfg(not(feature = "strict"))]
+    {
+        cast_identity_borrowed(ty).unwrap()
+    }
+}
+
+/// Return true if a [`Specializer`](crate::Specializer) over parameter `T`
+/// and return `U` would take an arm specialized on parameter `P` and return
+/// `R`.
+///
+/// This doesn't need a specializer to be built, so generic code can check
+/// whether a fast path exists before preparing its inputs.  The check is
+/// free after optimization.
+///
+/// ```rust
+/// use specializer::would_specialize;
+///
+/// fn has_fast_path<T: 'static>() -> bool {
+///     would_specialize::<T, String, u32, String>()
+/// }
+///
+/// assert!(has_fast_path::<u32>());
+/// assert!(!has_fast_path::<u8>());
+/// ```
+#[inline(always)]
+pub fn would_specialize<T, U, P, R>() -> bool
+where
+    T: 'static,
+    U: 'static,
+    P: 'static,
+    R: 'static,
+{
+    type_eq::<T, P>() && type_eq::<U, R>()
+}
+
+/// Return true if `T` and `U` are the same type.
+#[inline(always)]
+pub(crate) fn type_eq<T, U>() -> bool
+where
+    T: ?Sized + 'static,
+    U: ?Sized + 'static,
+{
+    TypeId::of::<T>() == TypeId::of::<U>()
+}
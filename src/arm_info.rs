@@ -0,0 +1,110 @@
+use core::{any::TypeId, fmt, panic::Location};
+
+/// Information about an arm registered in a [`DynSpecializer`]
+///
+/// Returned by [`DynSpecializer::arms()`].
+///
+/// [`DynSpecializer`]: crate::DynSpecializer
+/// [`DynSpecializer::arms()`]: crate::DynSpecializer::arms
+#[derive(Clone, Copy, Debug)]
+pub struct ArmInfo {
+    type_id: TypeId,
+    type_name: &'static str,
+    priority: i32,
+    location: Option<&'static Location<'static>>,
+    active: bool,
+}
+
+impl ArmInfo {
+    pub(crate) const fn new(
+        type_id: TypeId,
+        type_name: &'static str,
+        priority: i32,
+        location: Option<&'static Location<'static>>,
+        active: bool,
+    ) -> Self {
+        Self {
+            type_id,
+            type_name,
+            priority,
+            location,
+            active,
+        }
+    }
+
+    /// Return the [`TypeId`] of the parameter type the arm specializes on.
+    pub const fn param_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Return the name of the parameter type the arm specializes on.
+    pub const fn param_type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Return the priority the arm was registered with.
+    pub const fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Return the source location the arm was registered from.
+    ///
+    /// Returns `None` for arms registered from [`StaticArm`]s.
+    ///
+    /// [`StaticArm`]: crate::StaticArm
+    pub const fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// Return true if this is the arm dispatched to for its parameter type
+    /// (rather than being shadowed by a higher priority arm).
+    pub const fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+impl fmt::Display for ArmInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (priority {}", self.type_name, self.priority)?;
+
+        if !self.active {
+            f.write_str(", shadowed")?;
+        }
+
+        f.write_str(")")?;
+
+        if let Some(location) = self.location {
+            write!(f, " registered at {location}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ArmInfo {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "{=str} (priority {=i32}",
+            self.type_name,
+            self.priority
+        );
+
+        if !self.active {
+            defmt::write!(f, ", shadowed");
+        }
+
+        defmt::write!(f, ")");
+
+        if let Some(location) = self.location {
+            defmt::write!(
+                f,
+                " registered at {=str}:{=u32}:{=u32}",
+                location.file(),
+                location.line(),
+                location.column(),
+            );
+        }
+    }
+}
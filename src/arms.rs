@@ -0,0 +1,88 @@
+/// Tuple of closures specializing on their parameter types, for adding
+/// several arms at once
+///
+/// Implemented for tuples of up to 12 closures taking `'static` parameter
+/// types `P` (as a tuple) and returning `U`.  The closures are consulted in
+/// order.  See
+/// [`Specializer::specialize_many()`](crate::Specializer::specialize_many).
+///
+/// ```rust
+/// use specializer::Arms;
+///
+/// let arms = (|int: i32| int * 2, |string: String| string.len() as i32);
+///
+/// assert_eq!(arms.dispatch("Hello".to_owned()), Ok(5));
+/// ```
+pub trait Arms<T, U, P> {
+    /// Run the first closure taking `T`, or hand `ty` back if there is none.
+    fn dispatch(self, ty: T) -> Result<U, T>;
+
+    /// Return true if one of the closures takes `T`.
+    fn accepts() -> bool;
+}
+
+macro_rules! arms {
+    ($($f:ident($v:ident): $p:ident),*) => {
+        impl<T, U, $($f, $p),*> Arms<T, U, ($($p,)*)> for ($($f,)*)
+        where
+            T: 'static,
+            $($f: FnOnce($p) -> U, $p: 'static),*
+        {
+            #[inline(always)]
+            fn dispatch(self, ty: T) -> Result<U, T> {
+                let ($($v,)*) = self;
+
+                $(
+                    if crate::api::type_eq::<T, $p>() {
+                        crate::probe::taken::<$p>();
+
+                        let param = crate::cast_identity::<T, $p>(ty).unwrap();
+
+                        return Ok($v(param));
+                    }
+
+                    crate::probe::missed::<$p>();
+                )*
+
+                Err(ty)
+            }
+
+            #[inline(always)]
+            fn accepts() -> bool {
+                false $(|| crate::api::type_eq::<T, $p>())*
+            }
+        }
+    };
+}
+
+arms!(F1(f1): P1);
+arms!(F1(f1): P1, F2(f2): P2);
+arms!(F1(f1): P1, F2(f2): P2, F3(f3): P3);
+arms!(F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4);
+arms!(F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5);
+arms!(F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7
+);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7, F8(f8): P8
+);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7, F8(f8): P8, F9(f9): P9
+);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7, F8(f8): P8, F9(f9): P9, F10(f10): P10
+);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7, F8(f8): P8, F9(f9): P9, F10(f10): P10, F11(f11): P11
+);
+arms!(
+    F1(f1): P1, F2(f2): P2, F3(f3): P3, F4(f4): P4, F5(f5): P5, F6(f6): P6,
+    F7(f7): P7, F8(f8): P8, F9(f9): P9, F10(f10): P10, F11(f11): P11,
+    F12(f12): P12
+);
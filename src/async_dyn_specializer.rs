@@ -0,0 +1,126 @@
+use alloc::boxed::Box;
+use core::{fmt, future::Future, pin::Pin};
+
+use crate::DynSpecializer;
+
+type BoxFuture<U> = Pin<Box<dyn Future<Output = U> + Send>>;
+
+/// Runtime registry of async specialized behavior (Owned -> Owned)
+///
+/// Async counterpart of [`DynSpecializer`]: arms return futures, which are
+/// boxed at registration so that handlers for different types can be stored
+/// together, and [`dispatch()`](Self::dispatch) awaits the matching one.
+///
+/// ```rust
+/// use specializer::AsyncDynSpecializer;
+/// use pasts::Executor;
+///
+/// struct Ping(u32);
+/// struct Join(String);
+///
+/// let mut handlers = AsyncDynSpecializer::new();
+///
+/// handlers
+///     .register(|ping: Ping| async move { format!("pong {}", ping.0) })
+///     .register(|join: Join| async move { format!("welcome {}", join.0) });
+///
+/// Executor::default().block_on(async move {
+///     let pong = handlers.dispatch(Ping(1)).await;
+///     let welcome = handlers.dispatch(Join("ferris".to_owned())).await;
+///
+///     assert_eq!(pong.ok(), Some("pong 1".to_owned()));
+///     assert_eq!(welcome.ok(), Some("welcome ferris".to_owned()));
+///     assert!(handlers.dispatch(()).await.is_err());
+/// });
+/// ```
+pub struct AsyncDynSpecializer<U>(DynSpecializer<BoxFuture<U>>);
+
+impl<U> AsyncDynSpecializer<U> {
+    /// Create a new specializer without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self(DynSpecializer::new())
+    }
+
+    /// Register an async arm for parameter type `P` with the default priority
+    /// (0).
+    ///
+    /// Registering a second arm for the same parameter type and priority
+    /// replaces the first.
+    #[track_caller]
+    pub fn register<P, Fut>(
+        &mut self,
+        f: impl Fn(P) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        self.register_with_priority(0, f)
+    }
+
+    /// Register an async arm for parameter type `P` with a priority.
+    ///
+    /// See [`DynSpecializer::register_with_priority()`].
+    #[track_caller]
+    pub fn register_with_priority<P, Fut>(
+        &mut self,
+        priority: i32,
+        f: impl Fn(P) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        self.0.register_with_priority(priority, move |param: P| {
+            let future: BoxFuture<U> = Box::pin(f(param));
+
+            future
+        });
+        self
+    }
+
+    /// Return true if an arm is registered for parameter type `P`.
+    pub fn contains<P>(&self) -> bool
+    where
+        P: 'static,
+    {
+        self.0.contains::<P>()
+    }
+
+    /// Return the number of parameter types with registered arms.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run the arm registered for the type of `param` to completion.
+    ///
+    /// Returns `Err(param)` if no arm is registered for `T`.
+    #[inline]
+    pub async fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: 'static,
+    {
+        match self.0.dispatch(param) {
+            Ok(future) => Ok(future.await),
+            Err(param) => Err(param),
+        }
+    }
+}
+
+impl<U> Default for AsyncDynSpecializer<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> fmt::Debug for AsyncDynSpecializer<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsyncDynSpecializer").field(&self.0).finish()
+    }
+}
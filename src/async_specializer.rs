@@ -1,8 +1,18 @@
-use core::{any::TypeId, future, marker::PhantomData};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+use core::{future, marker::PhantomData};
+
+use crate::{
+    AsyncSpecializerBorrowed, AsyncSpecializerBorrowedParam,
+    AsyncSpecializerBorrowedReturn, AsyncSpecializerChain,
+    CastIdentityBorrowed,
+};
 
 /// Async specialized behavior runner (Owned -> Owned)
 #[derive(Debug)]
-pub struct AsyncSpecializer<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct AsyncSpecializer<T, U, F>(T, F, PhantomData<fn(T) -> U>, bool, bool);
 
 impl<T, U, F> AsyncSpecializer<T, U, F>
 where
@@ -13,7 +23,7 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -49,20 +59,23 @@ where
         P: 'static,
         R: 'static,
     {
-        let AsyncSpecializer(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
-            if TypeId::of::<T>() == TypeId::of::<P>()
-                && TypeId::of::<U>() == TypeId::of::<R>()
-            {
+        let AsyncSpecializer(ty, fallback, PhantomData, matched, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
 
                 return crate::cast_identity::<R, U>(f(param).await).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializer(ty, f, phantom_data)
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        AsyncSpecializer(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -107,11 +120,10 @@ where
         P: 'static,
         R: 'static,
     {
-        let AsyncSpecializer(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
-            if TypeId::of::<T>() == TypeId::of::<P>()
-                && TypeId::of::<U>() == TypeId::of::<R>()
-            {
+        let AsyncSpecializer(ty, fallback, PhantomData, matched, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
                 let param =
                     crate::cast_identity::<P, T>(p(param).await).unwrap();
@@ -120,10 +132,14 @@ where
                 return crate::cast_identity::<R, U>(r(ret).await).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializer(ty, f, phantom_data)
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        AsyncSpecializer(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -165,6 +181,37 @@ where
         self.specialize::<P, U>(f)
     }
 
+    /// Specialize on the parameter of the closure, which only borrows it.
+    ///
+    /// The parameter is dropped after the returned future completes.
+    ///
+    /// ```rust
+    /// use specializer::AsyncSpecializer;
+    /// use pasts::Executor;
+    ///
+    /// async fn len<T: 'static>(ty: T) -> usize {
+    ///     AsyncSpecializer::new(ty, async |_| 0)
+    ///         .specialize_param_ref(async |string: &String| string.len())
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(len("Hello".to_owned()).await, 5);
+    ///     assert_eq!(len(3).await, 0);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_param_ref<P>(
+        self,
+        f: impl AsyncFnOnce(&P) -> U,
+    ) -> AsyncSpecializer<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(async move |p: P| f(&p).await)
+    }
+
     /// Specialize on the return type of the closure.
     ///
     /// ```rust
@@ -288,9 +335,193 @@ where
         self.specialize_map::<T, R>(future::ready, f, r)
     }
 
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializer;
+    ///
+    /// async fn specialized<T: 'static>(ty: T) -> String {
+    ///     AsyncSpecializer::new(ty, async |_| "unknown".to_owned())
+    ///         .specialize_sync(|int: i32| (int * 2).to_string())
+    ///         .specialize_sync(|string: String| string)
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(3).await, "6");
+    ///     assert_eq!(specialized("Hello".to_owned()).await, "Hello");
+    ///     assert_eq!(specialized(()).await, "unknown");
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> AsyncSpecializer<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
+    /// Specialize on the parameter and the return type of a closure returning a
+    /// boxed future.
+    ///
+    /// Boxing keeps the arm's future out of the specializer's future type,
+    /// which allows arms to recurse back into the specializer and keeps long
+    /// chains from hitting type length limits.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializer;
+    ///
+    /// async fn describe<T: 'static>(ty: T) -> String {
+    ///     AsyncSpecializer::new(ty, async |_| "other".to_owned())
+    ///         .specialize_param(async |int: u32| int.to_string())
+    ///         .specialize_boxed(|option: Option<u32>| {
+    ///             Box::pin(async move {
+    ///                 let Some(int) = option else {
+    ///                     return "none".to_owned();
+    ///                 };
+    ///
+    ///                 format!("some {}", describe(int).await)
+    ///             })
+    ///         })
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(describe(Some(3u32)).await, "some 3");
+    ///     assert_eq!(describe(None::<u32>).await, "none");
+    ///     assert_eq!(describe(()).await, "other");
+    /// });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn specialize_boxed<'a, P, R>(
+        self,
+        f: impl FnOnce(P) -> Pin<Box<dyn Future<Output = R> + 'a>>,
+    ) -> AsyncSpecializer<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize::<P, R>(async move |p: P| f(p).await)
+    }
+
+    /// Convert into an [`AsyncSpecializerBorrowedParam`], keeping the arms
+    /// added so far, so that the next arms can take borrowed parameters.
+    #[inline(always)]
+    pub fn into_borrowed_param(self) -> AsyncSpecializerBorrowedParam<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+    {
+        AsyncSpecializerBorrowedParam::from_parts(
+            self.0, self.1, self.3, self.4,
+        )
+    }
+
+    /// Convert into an [`AsyncSpecializerBorrowedReturn`], keeping the arms
+    /// added so far, so that the next arms can return borrowed types.
+    #[inline(always)]
+    pub fn into_borrowed_return(self) -> AsyncSpecializerBorrowedReturn<T, U, F>
+    where
+        U: CastIdentityBorrowed<U>,
+    {
+        AsyncSpecializerBorrowedReturn::from_parts(
+            self.0, self.1, self.3, self.4,
+        )
+    }
+
+    /// Convert into an [`AsyncSpecializerBorrowed`], keeping the arms added so
+    /// far.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> AsyncSpecializerBorrowed<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+        U: CastIdentityBorrowed<U>,
+    {
+        AsyncSpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub async fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0).await
     }
+
+    /// Run the specializer, converting the result into `V`.
+    ///
+    /// ```rust
+    /// use specializer::AsyncSpecializer;
+    /// use pasts::Executor;
+    ///
+    /// async fn total<T: 'static>(ty: T) -> u64 {
+    ///     AsyncSpecializer::new(ty, async |_| 0u8)
+    ///         .specialize_param(async |(): ()| 1)
+    ///         .run_into()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(total(()).await, 1);
+    ///     assert_eq!(total(3).await, 0);
+    /// });
+    /// ```
+    #[inline]
+    pub async fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().await.into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](AsyncSpecializer::new) is dropped.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializer;
+    ///
+    /// async fn apply<T, U>(f: impl AsyncFnOnce(T) -> U, ty: T) -> U {
+    ///     f(ty).await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     let f = AsyncSpecializer::new(0, async |_| "other")
+    ///         .specialize_param(async |_: i32| "int")
+    ///         .into_fn();
+    ///
+    ///     assert_eq!(apply(f, 3).await, "int");
+    /// });
+    /// ```
+    #[inline(always)]
+    pub fn into_fn(self) -> impl AsyncFnOnce(T) -> U {
+        self.1
+    }
+}
+
+impl<T, U, F> AsyncSpecializer<T, U, F>
+where
+    F: AsyncFn(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Start building a chain of arms with a fallback function, before
+    /// having the parameter.
+    ///
+    /// See [`AsyncSpecializerChain::build()`].
+    #[inline(always)]
+    pub const fn builder(f: F) -> AsyncSpecializerChain<T, U, F> {
+        AsyncSpecializerChain::new(f)
+    }
 }
@@ -4,7 +4,13 @@ use crate::CastIdentityBorrowed;
 
 /// Async specialized behavior runner (Borrowed -> Borrowed)
 #[derive(Debug)]
-pub struct AsyncSpecializerBorrowed<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct AsyncSpecializerBorrowed<T, U, F>(
+    T,
+    F,
+    PhantomData<fn(T) -> U>,
+    bool,
+    bool,
+);
 
 impl<T, U, F> AsyncSpecializerBorrowed<T, U, F>
 where
@@ -15,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -56,21 +75,27 @@ where
         T: CastIdentityBorrowed<P>,
         R: CastIdentityBorrowed<U>,
     {
-        let AsyncSpecializerBorrowed(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
+        let Self(ty, fallback, PhantomData, matched, armed) = self;
+        let f = async move |t: T| -> U {
             if <R as CastIdentityBorrowed<U>>::is_same()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
 
-                return crate::cast_identity_borrowed::<R, U>(f(param).await)
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(f(param).await);
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowed(ty, f, phantom_data)
+        let matched = matched
+            || <R as CastIdentityBorrowed<U>>::is_same()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        AsyncSpecializerBorrowed(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -136,26 +161,29 @@ where
         R: CastIdentityBorrowed<U>,
         U: CastIdentityBorrowed<R>,
     {
-        let AsyncSpecializerBorrowed(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
+        let Self(ty, fallback, PhantomData, matched, armed) = self;
+        let f = async move |t: T| -> U {
             if <U as CastIdentityBorrowed<R>>::is_same()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
-                let param =
-                    crate::cast_identity_borrowed::<P, T>(p(param).await)
-                        .unwrap();
-                let ret = crate::cast_identity_borrowed::<U, R>(f(param).await)
-                    .unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
+                let param = crate::api::cast_matched::<P, T>(p(param).await);
+                let ret = crate::api::cast_matched::<U, R>(f(param).await);
 
-                return crate::cast_identity_borrowed::<R, U>(r(ret).await)
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(r(ret).await);
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowed(ty, f, phantom_data)
+        let matched = matched
+            || <U as CastIdentityBorrowed<R>>::is_same()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        AsyncSpecializerBorrowed(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -358,9 +386,95 @@ where
         self.specialize_map::<T, R>(future::ready, f, r)
     }
 
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerBorrowed;
+    ///
+    /// async fn specialized<T: 'static>(ty: &mut T) -> Option<&u32> {
+    ///     AsyncSpecializerBorrowed::new(ty, async |_| None)
+    ///         .specialize_sync(|int: &mut u32| -> Option<&u32> {
+    ///             *int += 1;
+    ///             Some(&*int)
+    ///         })
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(&mut 3u32).await, Some(&4));
+    ///     assert_eq!(specialized(&mut 3i32).await, None);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> AsyncSpecializerBorrowed<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        T: CastIdentityBorrowed<P>,
+        R: CastIdentityBorrowed<U>,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
     /// Run the specializer.
     #[inline]
     pub async fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0).await
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerBorrowedParam;
+    ///
+    /// async fn name<T: 'static>(ty: &T) -> Result<&'static str, &T> {
+    ///     AsyncSpecializerBorrowedParam::new(ty, async |_| unreachable!())
+    ///         .specialize_param(async |(): &()| "unit")
+    ///         .into_borrowed()
+    ///         .specialize_param(async |_: &String| "String")
+    ///         .try_run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(name(&()).await, Ok("unit"));
+    ///     assert_eq!(name(&String::new()).await, Ok("String"));
+    ///     assert_eq!(name(&3).await, Err(&3));
+    /// });
+    /// ```
+    #[inline]
+    pub async fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run().await)
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub async fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().await.into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](AsyncSpecializerBorrowed::new) is
+    /// dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl AsyncFnOnce(T) -> U {
+        self.1
+    }
 }
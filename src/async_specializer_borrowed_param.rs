@@ -1,6 +1,6 @@
-use core::{any::TypeId, future, marker::PhantomData};
+use core::{future, marker::PhantomData};
 
-use crate::CastIdentityBorrowed;
+use crate::{AsyncSpecializerBorrowed, CastIdentityBorrowed};
 
 /// Async specialized behavior runner (Borrowed -> Owned)
 #[derive(Debug)]
@@ -8,6 +8,8 @@ pub struct AsyncSpecializerBorrowedParam<T, U, F>(
     T,
     F,
     PhantomData<fn(T) -> U>,
+    bool,
+    bool,
 );
 
 impl<T, U, F> AsyncSpecializerBorrowedParam<T, U, F>
@@ -19,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -55,20 +70,33 @@ where
         T: CastIdentityBorrowed<P>,
         R: 'static,
     {
-        let AsyncSpecializerBorrowedParam(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
-            if TypeId::of::<U>() == TypeId::of::<R>()
+        let AsyncSpecializerBorrowedParam(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = async move |t: T| -> U {
+            if crate::api::type_eq::<U, R>()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
 
                 return crate::cast_identity::<R, U>(f(param).await).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowedParam(ty, f, phantom_data)
+        let matched = matched
+            || crate::api::type_eq::<U, R>()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        AsyncSpecializerBorrowedParam(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -119,24 +147,35 @@ where
         P: CastIdentityBorrowed<T>,
         R: 'static,
     {
-        let AsyncSpecializerBorrowedParam(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
-            if TypeId::of::<U>() == TypeId::of::<R>()
+        let AsyncSpecializerBorrowedParam(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = async move |t: T| -> U {
+            if crate::api::type_eq::<U, R>()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
-                let param =
-                    crate::cast_identity_borrowed::<P, T>(p(param).await)
-                        .unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
+                let param = crate::api::cast_matched::<P, T>(p(param).await);
                 let ret = crate::cast_identity::<U, R>(f(param).await).unwrap();
 
                 return crate::cast_identity::<R, U>(r(ret).await).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowedParam(ty, f, phantom_data)
+        let matched = matched
+            || crate::api::type_eq::<U, R>()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        AsyncSpecializerBorrowedParam(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -301,9 +340,106 @@ where
         self.specialize_map::<T, R>(future::ready, f, r)
     }
 
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerBorrowedParam;
+    ///
+    /// async fn specialized<T: 'static>(ty: &mut T) -> String {
+    ///     let fallback = async |_| "unknown".to_owned();
+    ///
+    ///     AsyncSpecializerBorrowedParam::new(ty, fallback)
+    ///         .specialize_sync(|int: &mut i32| (*int * 2).to_string())
+    ///         .specialize_sync(|string: &mut String| string.clone())
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(&mut 3).await, "6");
+    ///     assert_eq!(specialized(&mut "Hello".to_owned()).await, "Hello");
+    ///     assert_eq!(specialized(&mut ()).await, "unknown");
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> AsyncSpecializerBorrowedParam<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        T: CastIdentityBorrowed<P>,
+        R: 'static,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
+    /// Convert into an [`AsyncSpecializerBorrowed`], keeping the arms added so
+    /// far, so that the next arms can return borrowed types.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> AsyncSpecializerBorrowed<T, U, F>
+    where
+        U: CastIdentityBorrowed<U>,
+    {
+        AsyncSpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub async fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0).await
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use specializer::AsyncSpecializerBorrowedParam;
+    /// use pasts::Executor;
+    ///
+    /// async fn double<T: 'static>(ty: &mut T) -> Result<(), &mut T> {
+    ///     AsyncSpecializerBorrowedParam::new(ty, async |_| unreachable!())
+    ///         .specialize_param(async |int: &mut i32| *int *= 2)
+    ///         .try_run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     let mut int = 3;
+    ///
+    ///     assert_eq!(double(&mut int).await, Ok(()));
+    ///     assert_eq!(int, 6);
+    ///     assert_eq!(double(&mut 3u8).await, Err(&mut 3));
+    /// });
+    /// ```
+    #[inline]
+    pub async fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run().await)
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub async fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().await.into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](AsyncSpecializerBorrowedParam::new) is
+    /// dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl AsyncFnOnce(T) -> U {
+        self.1
+    }
 }
@@ -1,6 +1,6 @@
-use core::{any::TypeId, future, marker::PhantomData};
+use core::{future, marker::PhantomData};
 
-use crate::CastIdentityBorrowed;
+use crate::{AsyncSpecializerBorrowed, CastIdentityBorrowed};
 
 /// Async specialized behavior runner (Owned -> Borrowed)
 #[derive(Debug)]
@@ -8,6 +8,8 @@ pub struct AsyncSpecializerBorrowedReturn<T, U, F>(
     T,
     F,
     PhantomData<fn(T) -> U>,
+    bool,
+    bool,
 );
 
 impl<T, U, F> AsyncSpecializerBorrowedReturn<T, U, F>
@@ -19,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -60,21 +75,33 @@ where
         P: 'static,
         R: CastIdentityBorrowed<U>,
     {
-        let AsyncSpecializerBorrowedReturn(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
+        let AsyncSpecializerBorrowedReturn(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = async move |t: T| -> U {
             if <R as CastIdentityBorrowed<U>>::is_same()
-                && TypeId::of::<T>() == TypeId::of::<P>()
+                && crate::api::type_eq::<T, P>()
             {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
 
-                return crate::cast_identity_borrowed::<R, U>(f(param).await)
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(f(param).await);
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowedReturn(ty, f, phantom_data)
+        let matched = matched
+            || <R as CastIdentityBorrowed<U>>::is_same()
+                && crate::api::type_eq::<T, P>();
+
+        AsyncSpecializerBorrowedReturn(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -124,25 +151,36 @@ where
         R: CastIdentityBorrowed<U>,
         U: CastIdentityBorrowed<R>,
     {
-        let AsyncSpecializerBorrowedReturn(ty, fallback, phantom_data) = self;
-        let f = async |t: T| -> U {
+        let AsyncSpecializerBorrowedReturn(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = async move |t: T| -> U {
             if <U as CastIdentityBorrowed<R>>::is_same()
-                && TypeId::of::<T>() == TypeId::of::<P>()
+                && crate::api::type_eq::<T, P>()
             {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
                 let param =
                     crate::cast_identity::<P, T>(p(param).await).unwrap();
-                let ret = crate::cast_identity_borrowed::<U, R>(f(param).await)
-                    .unwrap();
+                let ret = crate::api::cast_matched::<U, R>(f(param).await);
 
-                return crate::cast_identity_borrowed::<R, U>(r(ret).await)
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(r(ret).await);
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t).await
         };
 
-        AsyncSpecializerBorrowedReturn(ty, f, phantom_data)
+        let matched = matched
+            || <U as CastIdentityBorrowed<R>>::is_same()
+                && crate::api::type_eq::<T, P>();
+
+        AsyncSpecializerBorrowedReturn(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -308,9 +346,104 @@ where
         self.specialize_map::<T, R>(future::ready, f, r)
     }
 
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerBorrowedReturn;
+    ///
+    /// async fn specialized<T: 'static>(ty: T, b: &u32) -> Option<&u32> {
+    ///     AsyncSpecializerBorrowedReturn::new(ty, async |_| None)
+    ///         .specialize_sync(|_: i32| -> Option<&u32> { Some(b) })
+    ///         .run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(3, &5).await, Some(&5));
+    ///     assert_eq!(specialized((), &5).await, None);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> AsyncSpecializerBorrowedReturn<T, U, impl AsyncFnOnce(T) -> U>
+    where
+        P: 'static,
+        R: CastIdentityBorrowed<U>,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
+    /// Convert into an [`AsyncSpecializerBorrowed`], keeping the arms added so
+    /// far, so that the next arms can take borrowed parameters.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> AsyncSpecializerBorrowed<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+    {
+        AsyncSpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub async fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0).await
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializer;
+    ///
+    /// static BYTES: [u8; 3] = [1, 2, 3];
+    ///
+    /// async fn lookup<T: 'static>(ty: T) -> Result<Option<&'static u8>, T> {
+    ///     AsyncSpecializer::new(ty, async |_| unreachable!())
+    ///         .specialize_param(async |(): ()| None)
+    ///         .into_borrowed_return()
+    ///         .specialize_param(async |index: usize| BYTES.get(index))
+    ///         .try_run()
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(lookup(()).await, Ok(None));
+    ///     assert_eq!(lookup(1usize).await, Ok(Some(&2)));
+    ///     assert_eq!(lookup(1u8).await, Err(1));
+    /// });
+    /// ```
+    #[inline]
+    pub async fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run().await)
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub async fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().await.into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](AsyncSpecializerBorrowedReturn::new)
+    /// is dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl AsyncFnOnce(T) -> U {
+        self.1
+    }
 }
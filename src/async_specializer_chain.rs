@@ -0,0 +1,416 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
+use core::{future, marker::PhantomData};
+
+/// Reusable async specialized behavior runner (Owned -> Owned)
+///
+/// Like [`AsyncSpecializer`](crate::AsyncSpecializer), but built from
+/// [`AsyncFn`] arms without the parameter, so that it can be built once and
+/// run any number of times with
+/// [`run_with()`](AsyncSpecializerChain::run_with).
+#[derive(Clone, Copy, Debug)]
+pub struct AsyncSpecializerChain<T, U, F>(F, PhantomData<fn(T) -> U>, bool);
+
+impl<T, U, F> AsyncSpecializerChain<T, U, F>
+where
+    F: AsyncFn(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer chain with a fallback function.
+    ///
+    /// The chain is built once, and since [`run_with()`](Self::run_with) only
+    /// borrows it, it can be shared by every request it dispatches.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// fn router<T: 'static>() -> impl AsyncFn(T) -> u16 {
+    ///     AsyncSpecializerChain::new(async |_| 404)
+    ///         .specialize_param(async |path: &'static str| path.len() as u16)
+    ///         .build()
+    /// }
+    ///
+    /// let paths = router();
+    /// let ids = router::<u32>();
+    ///
+    /// Executor::default().block_on(async move {
+    ///     for _ in 0..1000 {
+    ///         assert_eq!(paths("/index.html").await, 11);
+    ///         assert_eq!(ids(42).await, 404);
+    ///     }
+    /// });
+    /// ```
+    #[inline(always)]
+    pub const fn new(f: F) -> Self {
+        Self(f, PhantomData, false)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T> + From<u8>,
+    /// {
+    ///     let chain = AsyncSpecializerChain::new(async |ty| U::from(ty))
+    ///         .specialize(async |int: i32| -> i32 { int * 2 })
+    ///         .specialize_param(async |int: u8| U::from(int * 3));
+    ///     let mut output = Vec::new();
+    ///
+    ///     for ty in tys {
+    ///         output.push(chain.run_with(ty).await);
+    ///     }
+    ///
+    ///     output
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(vec![1, 3]).await, [1, 3]);
+    ///     assert_eq!(specialized::<i32, i32>(vec![1, 3]).await, [2, 6]);
+    ///     assert_eq!(specialized::<u8, i32>(vec![1, 3]).await, [3, 9]);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl AsyncFn(P) -> R,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AsyncSpecializerChain(fallback, PhantomData, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return crate::cast_identity::<R, U>(f(param).await).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t).await
+        };
+
+        AsyncSpecializerChain(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::future;
+    ///
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerChain::new(async |ty| U::from(ty))
+    ///         .specialize_map(
+    ///             async |int: u8| int * 3,
+    ///             async |ty| U::from(ty),
+    ///             future::ready::<U>,
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<u8, i32>(3).await, 9);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl AsyncFn(P) -> P,
+        f: impl AsyncFn(T) -> U,
+        r: impl AsyncFn(R) -> R,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AsyncSpecializerChain(fallback, PhantomData, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+                let param =
+                    crate::cast_identity::<P, T>(p(param).await).unwrap();
+                let ret = crate::cast_identity::<U, R>(f(param).await).unwrap();
+
+                return crate::cast_identity::<R, U>(r(ret).await).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t).await
+        };
+
+        AsyncSpecializerChain(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T: 'static>(ty: T) -> String {
+    ///     AsyncSpecializerChain::new(async |_| "unknown".to_owned())
+    ///         .specialize_param(async |int: i32| (int * 2).to_string())
+    ///         .specialize_param(async |string: String| string)
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(3).await, "6");
+    ///     assert_eq!(specialized("Hello".to_owned()).await, "Hello");
+    ///     assert_eq!(specialized(()).await, "unknown");
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl AsyncFn(P) -> U,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T>(int: i32) -> T
+    /// where
+    ///     T: 'static + Default,
+    /// {
+    ///     let fallback = async |_: i32| -> T { Default::default() };
+    ///
+    ///     AsyncSpecializerChain::new(fallback)
+    ///         .specialize_return(async |int| -> i32 { int * 2 })
+    ///         .specialize_return(async |int| -> String { int.to_string() })
+    ///         .run_with(int)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i32>(3).await, 6);
+    ///     assert_eq!(specialized::<String>(3).await, "3");
+    ///     assert_eq!(specialized::<u8>(3).await, 0);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl AsyncFn(T) -> R,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerChain::new(async |ty| U::from(ty))
+    ///         .specialize_map_param(
+    ///             async |int: u8| int * 3,
+    ///             async |ty| U::from(ty),
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<u8, i32>(3).await, 9);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl AsyncFn(P) -> P,
+        f: impl AsyncFn(T) -> U,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, future::ready)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerChain::new(async |ty| U::from(ty))
+    ///         .specialize_map_return(
+    ///             async |ty| U::from(ty),
+    ///             async |int: i16| int * 2,
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i8, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<i8, i16>(3).await, 6);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl AsyncFn(T) -> U,
+        r: impl AsyncFn(R) -> R,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(future::ready, f, r)
+    }
+
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// async fn specialized<T: 'static>(tys: Vec<T>) -> Vec<String> {
+    ///     let fallback = async |_| "unknown".to_owned();
+    ///     let chain = AsyncSpecializerChain::new(fallback)
+    ///         .specialize_sync(|int: i32| (int * 2).to_string());
+    ///     let mut output = Vec::new();
+    ///
+    ///     for ty in tys {
+    ///         output.push(chain.run_with(ty).await);
+    ///     }
+    ///
+    ///     output
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(vec![1, 3]).await, ["2", "6"]);
+    ///     assert_eq!(specialized(vec![()]).await, ["unknown"]);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        f: impl Fn(P) -> R,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
+    /// Specialize on the parameter and the return type of a closure returning a
+    /// boxed future.
+    ///
+    /// Boxing keeps the arm's future out of the specializer's future type,
+    /// which allows arms to recurse back into the specializer and keeps long
+    /// chains from hitting type length limits.
+    ///
+    /// ```rust
+    /// use std::{future::Future, pin::Pin};
+    ///
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerChain;
+    ///
+    /// fn double(int: i32) -> Pin<Box<dyn Future<Output = i32>>> {
+    ///     Box::pin(async move { int * 2 })
+    /// }
+    ///
+    /// let chain = AsyncSpecializerChain::new(async |int: i32| int)
+    ///     .specialize_boxed(double);
+    ///
+    /// Executor::default().block_on(async move {
+    ///     assert_eq!(chain.run_with(3).await, 6);
+    /// });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn specialize_boxed<'a, P, R>(
+        self,
+        f: impl Fn(P) -> Pin<Box<dyn Future<Output = R> + 'a>>,
+    ) -> AsyncSpecializerChain<T, U, impl AsyncFn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize::<P, R>(async move |p: P| f(p).await)
+    }
+
+    /// Run the specializer chain on `params`.
+    #[inline]
+    pub async fn run_with(&self, params: T) -> U {
+        crate::trace::fallback::<T>(self.2);
+
+        (self.0)(params).await
+    }
+
+    /// Finish building the chain, returning it as an async function.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializer;
+    ///
+    /// fn prepare<T: 'static>() -> impl AsyncFn(T) -> &'static str {
+    ///     AsyncSpecializer::builder(async |_: T| "other")
+    ///         .specialize_param(async |_: i32| "int")
+    ///         .build()
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     let describe = prepare::<i32>();
+    ///
+    ///     assert_eq!(describe(1).await, "int");
+    ///     assert_eq!(describe(2).await, "int");
+    ///     assert_eq!(prepare::<u8>()(3).await, "other");
+    /// });
+    /// ```
+    #[inline(always)]
+    pub fn build(self) -> F {
+        self.0
+    }
+}
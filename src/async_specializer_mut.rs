@@ -0,0 +1,353 @@
+use core::{future, marker::PhantomData};
+
+/// Reusable async specialized behavior runner with stateful arms (Owned ->
+/// Owned)
+///
+/// Like [`AsyncSpecializer`](crate::AsyncSpecializer), but built from
+/// [`AsyncFn`] arms without the parameter, so that it can be built once and
+/// run any number of times with
+/// [`run_with()`](AsyncSpecializerMut::run_with).
+#[derive(Debug)]
+pub struct AsyncSpecializerMut<T, U, F>(F, PhantomData<fn(T) -> U>, bool);
+
+impl<T, U, F> AsyncSpecializerMut<T, U, F>
+where
+    F: AsyncFnMut(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn count<T: 'static>(tys: Vec<T>) -> (usize, usize) {
+    ///     let (mut ints, mut others) = (0, 0);
+    ///     let fallback = async |_| others += 1;
+    ///     let mut specializer = AsyncSpecializerMut::new(fallback)
+    ///         .specialize_param(async |_: i32| ints += 1);
+    ///
+    ///     for ty in tys {
+    ///         specializer.run_with(ty).await;
+    ///     }
+    ///
+    ///     drop(specializer);
+    ///     (ints, others)
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(count(vec![1, 2, 3]).await, (3, 0));
+    ///     assert_eq!(count(vec!["a", "b"]).await, (0, 2));
+    /// });
+    /// ```
+    #[inline(always)]
+    pub const fn new(f: F) -> Self {
+        Self(f, PhantomData, false)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T> + From<u8>,
+    /// {
+    ///     let fallback = async |ty| U::from(ty);
+    ///     let mut specializer = AsyncSpecializerMut::new(fallback)
+    ///         .specialize(async |int: i32| -> i32 { int * 2 })
+    ///         .specialize_param(async |int: u8| U::from(int * 3));
+    ///     let mut output = Vec::new();
+    ///
+    ///     for ty in tys {
+    ///         output.push(specializer.run_with(ty).await);
+    ///     }
+    ///
+    ///     output
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(vec![1, 3]).await, [1, 3]);
+    ///     assert_eq!(specialized::<i32, i32>(vec![1, 3]).await, [2, 6]);
+    ///     assert_eq!(specialized::<u8, i32>(vec![1, 3]).await, [3, 9]);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        mut f: impl AsyncFnMut(P) -> R,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AsyncSpecializerMut(mut fallback, PhantomData, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return crate::cast_identity::<R, U>(f(param).await).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t).await
+        };
+
+        AsyncSpecializerMut(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::future;
+    ///
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerMut::new(async |ty| U::from(ty))
+    ///         .specialize_map(
+    ///             async |int: u8| int * 3,
+    ///             async |ty| U::from(ty),
+    ///             future::ready::<U>,
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<u8, i32>(3).await, 9);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        mut p: impl AsyncFnMut(P) -> P,
+        mut f: impl AsyncFnMut(T) -> U,
+        mut r: impl AsyncFnMut(R) -> R,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let AsyncSpecializerMut(mut fallback, PhantomData, armed) = self;
+        let f = async move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+                let param =
+                    crate::cast_identity::<P, T>(p(param).await).unwrap();
+                let ret = crate::cast_identity::<U, R>(f(param).await).unwrap();
+
+                return crate::cast_identity::<R, U>(r(ret).await).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t).await
+        };
+
+        AsyncSpecializerMut(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T: 'static>(ty: T) -> String {
+    ///     AsyncSpecializerMut::new(async |_| "unknown".to_owned())
+    ///         .specialize_param(async |int: i32| (int * 2).to_string())
+    ///         .specialize_param(async |string: String| string)
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized(3).await, "6");
+    ///     assert_eq!(specialized("Hello".to_owned()).await, "Hello");
+    ///     assert_eq!(specialized(()).await, "unknown");
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl AsyncFnMut(P) -> U,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T>(int: i32) -> T
+    /// where
+    ///     T: 'static + Default,
+    /// {
+    ///     let fallback = async |_: i32| -> T { Default::default() };
+    ///
+    ///     AsyncSpecializerMut::new(fallback)
+    ///         .specialize_return(async |int| -> i32 { int * 2 })
+    ///         .specialize_return(async |int| -> String { int.to_string() })
+    ///         .run_with(int)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i32>(3).await, 6);
+    ///     assert_eq!(specialized::<String>(3).await, "3");
+    ///     assert_eq!(specialized::<u8>(3).await, 0);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl AsyncFnMut(T) -> R,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerMut::new(async |ty| U::from(ty))
+    ///         .specialize_map_param(
+    ///             async |int: u8| int * 3,
+    ///             async |ty| U::from(ty),
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i16, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<u8, i32>(3).await, 9);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl AsyncFnMut(P) -> P,
+        f: impl AsyncFnMut(T) -> U,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, future::ready)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn specialized<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     AsyncSpecializerMut::new(async |ty| U::from(ty))
+    ///         .specialize_map_return(
+    ///             async |ty| U::from(ty),
+    ///             async |int: i16| int * 2,
+    ///         )
+    ///         .run_with(ty)
+    ///         .await
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(specialized::<i8, i32>(3).await, 3);
+    ///     assert_eq!(specialized::<i8, i16>(3).await, 6);
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl AsyncFnMut(T) -> U,
+        r: impl AsyncFnMut(R) -> R,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(future::ready, f, r)
+    }
+
+    /// Specialize on the parameter and the return type of a synchronous
+    /// closure, without wrapping it in an async one.
+    ///
+    /// ```rust
+    /// use pasts::Executor;
+    /// use specializer::AsyncSpecializerMut;
+    ///
+    /// async fn count<T: 'static>(tys: Vec<T>) -> (usize, usize) {
+    ///     let (mut ints, mut others) = (0, 0);
+    ///     let fallback = async |_| others += 1;
+    ///     let mut specializer = AsyncSpecializerMut::new(fallback)
+    ///         .specialize_sync(|_: i32| ints += 1);
+    ///
+    ///     for ty in tys {
+    ///         specializer.run_with(ty).await;
+    ///     }
+    ///
+    ///     drop(specializer);
+    ///     (ints, others)
+    /// }
+    ///
+    /// Executor::default().block_on(async {
+    ///     assert_eq!(count(vec![1, 2, 3]).await, (3, 0));
+    ///     assert_eq!(count(vec!["a", "b"]).await, (0, 2));
+    /// });
+    /// ```
+    #[inline]
+    pub fn specialize_sync<P, R>(
+        self,
+        mut f: impl FnMut(P) -> R,
+    ) -> AsyncSpecializerMut<T, U, impl AsyncFnMut(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize::<P, R>(move |p: P| future::ready(f(p)))
+    }
+
+    /// Run the specializer on `params`.
+    #[inline]
+    pub async fn run_with(&mut self, params: T) -> U {
+        crate::trace::fallback::<T>(self.2);
+
+        (self.0)(params).await
+    }
+}
@@ -0,0 +1,110 @@
+use core::fmt;
+
+use crate::CastIdentityBorrowed;
+
+/// A family of types differing only by a lifetime
+///
+/// Implemented on a `'static` marker type (the "brand"), this opts a type
+/// containing borrows (like `MyStruct<'a>`) into specialization through
+/// [`Branded`], since it can't be identified by its own
+/// [`TypeId`](core::any::TypeId).
+///
+/// ```rust
+/// use specializer::TypeFamily;
+///
+/// struct Token<'a>(&'a str);
+///
+/// enum TokenFamily {}
+///
+/// impl TypeFamily for TokenFamily {
+///     type Of<'a> = Token<'a>;
+/// }
+/// ```
+pub trait TypeFamily: 'static {
+    /// The member of the family with lifetime `'a`.
+    type Of<'a>;
+}
+
+/// Adapter for specializing on types containing borrows
+///
+/// [`CastIdentityBorrowed`] is implemented from `Branded<'a, F>` to
+/// `Branded<'a, G>` for every pair of [`TypeFamily`] brands, comparing the
+/// brands instead of the (non-`'static`) wrapped types, so the borrowed
+/// specializers can dispatch on them.
+///
+/// ```rust
+/// use specializer::{Branded, SpecializerBorrowedParam, TypeFamily};
+///
+/// struct Token<'a>(&'a str);
+///
+/// enum TokenFamily {}
+///
+/// impl TypeFamily for TokenFamily {
+///     type Of<'a> = Token<'a>;
+/// }
+///
+/// struct Span<'a>(&'a str, usize);
+///
+/// enum SpanFamily {}
+///
+/// impl TypeFamily for SpanFamily {
+///     type Of<'a> = Span<'a>;
+/// }
+///
+/// fn describe<'a, F: TypeFamily>(value: Branded<'a, F>) -> String {
+///     SpecializerBorrowedParam::new(value, |_| "unknown".to_owned())
+///         .specialize_param(|Branded(token): Branded<'a, TokenFamily>| {
+///             format!("token {}", token.0)
+///         })
+///         .specialize_param(|Branded(span): Branded<'a, SpanFamily>| {
+///             format!("span {} at {}", span.0, span.1)
+///         })
+///         .run()
+/// }
+///
+/// let source = "let x".to_owned();
+///
+/// assert_eq!(
+///     describe(Branded::<TokenFamily>(Token(&source[..3]))),
+///     "token let",
+/// );
+/// assert_eq!(
+///     describe(Branded::<SpanFamily>(Span(&source[4..], 4))),
+///     "span x at 4",
+/// );
+/// ```
+pub struct Branded<'a, F: TypeFamily>(pub F::Of<'a>);
+
+impl<F> fmt::Debug for Branded<'_, F>
+where
+    F: TypeFamily,
+    for<'a> F::Of<'a>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Branded").field(&self.0).finish()
+    }
+}
+
+impl<'a, F, G> CastIdentityBorrowed<Branded<'a, G>> for Branded<'a, F>
+where
+    F: TypeFamily,
+    G: TypeFamily,
+{
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    fn cast_identity(self) -> Option<Branded<'a, G>> {
+        // Function pointers that are generic over the lifetime are `'static`,
+        // so the identity function can be cast instead of the value
+        let identity: for<'b> fn(F::Of<'b>, &'b ()) -> F::Of<'b> = |v, _| v;
+        let cast = crate::cast_identity::<
+            for<'b> fn(F::Of<'b>, &'b ()) -> F::Of<'b>,
+            for<'b> fn(F::Of<'b>, &'b ()) -> G::Of<'b>,
+        >(identity)?;
+
+        Some(Branded(cast(self.0, &())))
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        crate::api::type_eq::<F, G>()
+    }
+}
@@ -0,0 +1,51 @@
+use core::{any::TypeId, cell::Cell, fmt};
+
+use crate::{DynSpecializer, dyn_specializer::DynArm};
+
+/// Dispatcher for a [`DynSpecializer`] that caches the last matched arm
+///
+/// Created with [`DynSpecializer::cached()`].
+pub struct CachedDispatch<'a, U> {
+    spec: &'a DynSpecializer<U>,
+    last: Cell<Option<(TypeId, &'a DynArm<U>)>>,
+}
+
+impl<'a, U> CachedDispatch<'a, U> {
+    pub(crate) const fn new(spec: &'a DynSpecializer<U>) -> Self {
+        Self {
+            spec,
+            last: Cell::new(None),
+        }
+    }
+
+    /// Run the arm registered for the type of `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for `T`.
+    #[inline]
+    pub fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        let arm = match self.last.get() {
+            Some((last, arm)) if last == type_id => Some(arm),
+            _ => self.spec.arm(type_id),
+        };
+        let Some(arm) = crate::trace::found::<T, _>(arm) else {
+            return Err(param);
+        };
+
+        self.last.set(Some((type_id, arm)));
+
+        Ok(arm(&mut Some(param)))
+    }
+}
+
+impl<U> fmt::Debug for CachedDispatch<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedDispatch")
+            .field("spec", &self.spec)
+            .field("last", &self.last.get().map(|(type_id, _)| type_id))
+            .finish()
+    }
+}
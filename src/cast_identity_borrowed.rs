@@ -1,4 +1,9 @@
-use core::{any::TypeId, pin::Pin, task::Poll};
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+};
+use core::{pin::Pin, task::Poll};
 
 /// Identity cast on a borrowed type
 ///
@@ -87,31 +92,33 @@ pub trait CastIdentityBorrowed<U>: Sized {
 
 impl<'a, T, U> CastIdentityBorrowed<&'a U> for &'a T
 where
-    T: 'static,
-    U: 'static,
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<&'a U> {
         crate::cast_identity_ref(self)
     }
 
     #[inline(always)]
     fn is_same() -> bool {
-        TypeId::of::<U>() == TypeId::of::<T>()
+        crate::api::type_eq::<U, T>()
     }
 }
 
 impl<'a, T, U> CastIdentityBorrowed<&'a mut U> for &'a mut T
 where
-    T: 'static,
-    U: 'static,
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<&'a mut U> {
         crate::cast_identity_mut(self)
     }
 
     #[inline(always)]
     fn is_same() -> bool {
-        TypeId::of::<U>() == TypeId::of::<T>()
+        crate::api::type_eq::<U, T>()
     }
 }
 
@@ -120,13 +127,14 @@ where
     T: 'static + Unpin,
     U: 'static + Unpin,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<Pin<&'a U>> {
         Some(Pin::new(crate::cast_identity_ref(self.get_ref())?))
     }
 
     #[inline(always)]
     fn is_same() -> bool {
-        TypeId::of::<U>() == TypeId::of::<T>()
+        crate::api::type_eq::<U, T>()
     }
 }
 
@@ -135,13 +143,14 @@ where
     T: 'static + Unpin,
     U: 'static + Unpin,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<Pin<&'a mut U>> {
         Some(Pin::new(crate::cast_identity_mut(self.get_mut())?))
     }
 
     #[inline(always)]
     fn is_same() -> bool {
-        TypeId::of::<U>() == TypeId::of::<T>()
+        crate::api::type_eq::<U, T>()
     }
 }
 
@@ -149,6 +158,7 @@ impl<T, U> CastIdentityBorrowed<Option<U>> for Option<T>
 where
     T: CastIdentityBorrowed<U>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<Option<U>> {
         Some(if let Some(inner) = self {
             Some(crate::cast_identity_borrowed(inner)?)
@@ -167,6 +177,7 @@ impl<T, U> CastIdentityBorrowed<Poll<U>> for Poll<T>
 where
     T: CastIdentityBorrowed<U>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<Poll<U>> {
         Some(if let Poll::Ready(inner) = self {
             Poll::Ready(crate::cast_identity_borrowed(inner)?)
@@ -186,6 +197,7 @@ where
     T: CastIdentityBorrowed<U>,
     E: CastIdentityBorrowed<F>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<Result<U, F>> {
         Some(match self {
             Ok(inner) => Ok(crate::cast_identity_borrowed(inner)?),
@@ -200,10 +212,53 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a, B, C> CastIdentityBorrowed<Cow<'a, C>> for Cow<'a, B>
+where
+    B: 'static + ToOwned + ?Sized,
+    C: 'static + ToOwned + ?Sized,
+    B::Owned: 'static,
+    C::Owned: 'static,
+{
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    fn cast_identity(self) -> Option<Cow<'a, C>> {
+        Some(match self {
+            Cow::Borrowed(borrowed) => {
+                Cow::Borrowed(crate::cast_identity_ref(borrowed)?)
+            }
+            Cow::Owned(owned) => Cow::Owned(crate::cast_identity(owned)?),
+        })
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        crate::api::type_eq::<C, B>()
+    }
+}
+
+/// The cast moves the value into a new allocation, since reusing the box (as
+/// `Box<dyn Any>::downcast()` does) would require `T: 'static`.
+#[cfg(feature = "alloc")]
+impl<T, U> CastIdentityBorrowed<Box<U>> for Box<T>
+where
+    T: CastIdentityBorrowed<U>,
+{
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    fn cast_identity(self) -> Option<Box<U>> {
+        Some(Box::new(crate::cast_identity_borrowed(*self)?))
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        <T as CastIdentityBorrowed<U>>::is_same()
+    }
+}
+
 impl<T, U> CastIdentityBorrowed<(U,)> for (T,)
 where
     T: CastIdentityBorrowed<U>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<(U,)> {
         let (a,) = self;
 
@@ -221,6 +276,7 @@ where
     T: CastIdentityBorrowed<U>,
     V: CastIdentityBorrowed<W>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<(U, W)> {
         let (a, b) = self;
 
@@ -243,6 +299,7 @@ where
     V: CastIdentityBorrowed<W>,
     X: CastIdentityBorrowed<Y>,
 {
+    #[cfg_attr(feature = "inline-always", inline(always))]
     fn cast_identity(self) -> Option<(U, W, Y)> {
         let (a, b, c) = self;
 
@@ -0,0 +1,71 @@
+use bytemuck::Pod;
+
+/// Attempt to reinterpret owned `T` as `U` with the same size.
+///
+/// Unlike [`cast_identity()`](crate::cast_identity), this succeeds for
+/// distinct types, as long as both are plain old data with identical size.
+/// Returns `None` if the sizes differ.
+///
+/// ```rust
+/// fn as_u32<T: bytemuck::Pod>(t: T) -> Option<u32> {
+///     specializer::cast_layout::<T, u32>(t)
+/// }
+///
+/// assert_eq!(as_u32([1u8, 0, 0, 0]), Some(u32::from_ne_bytes([1, 0, 0, 0])));
+/// assert_eq!(as_u32(1.0f32), Some(1.0f32.to_bits()));
+/// assert_eq!(as_u32(1u64), None);
+/// ```
+#[inline(always)]
+pub fn cast_layout<T, U>(ty: T) -> Option<U>
+where
+    T: Pod,
+    U: Pod,
+{
+    bytemuck::try_cast(ty).ok()
+}
+
+/// Attempt to reinterpret `&T` as `&U` with the same size.
+///
+/// Returns `None` if the sizes differ, or if `ty` isn't sufficiently aligned
+/// for `U`.
+///
+/// ```rust
+/// use specializer::cast_layout_ref;
+///
+/// let bits = 1.5f32.to_bits();
+/// let bytes = [1u8, 2, 3, 4];
+///
+/// assert_eq!(cast_layout_ref::<u32, f32>(&bits), Some(&1.5));
+/// assert_eq!(cast_layout_ref::<_, [i8; 4]>(&bytes), Some(&[1, 2, 3, 4]));
+/// assert_eq!(cast_layout_ref::<_, u64>(&bytes), None);
+/// ```
+#[inline(always)]
+pub fn cast_layout_ref<T, U>(ty: &T) -> Option<&U>
+where
+    T: Pod,
+    U: Pod,
+{
+    bytemuck::try_cast_ref(ty).ok()
+}
+
+/// Attempt to reinterpret `&[T]` as `&[U]` covering the same bytes.
+///
+/// Returns `None` if the byte length isn't a multiple of the size of `U`, or
+/// if `ty` isn't sufficiently aligned for `U`.
+///
+/// ```rust
+/// let words = [0x0102_0304u32, 0x0506_0708];
+/// let bytes = specializer::cast_layout_slice::<u32, u8>(&words).unwrap();
+///
+/// assert_eq!(bytes.len(), 8);
+/// assert_eq!(&bytes[..4], &0x0102_0304u32.to_ne_bytes());
+/// assert_eq!(specializer::cast_layout_slice::<u8, [u8; 3]>(bytes), None);
+/// ```
+#[inline(always)]
+pub fn cast_layout_slice<T, U>(ty: &[T]) -> Option<&[U]>
+where
+    T: Pod,
+    U: Pod,
+{
+    bytemuck::try_cast_slice(ty).ok()
+}
@@ -0,0 +1,62 @@
+use castaway::LifetimeFree;
+
+use crate::CastIdentityBorrowed;
+
+/// Adapter for casting a (possibly borrowed) value with
+/// [`castaway`](https://docs.rs/castaway)
+///
+/// [`CastIdentityBorrowed`] is implemented from `Castaway<T>` to any type
+/// implementing [`castaway::LifetimeFree`], without requiring `T: 'static`,
+/// so the borrowed specializers can dispatch on values that may contain
+/// borrows to arms taking lifetime-free types.
+///
+/// ```rust
+/// use specializer::{Castaway, SpecializerBorrowedParam};
+///
+/// fn describe<T>(value: T) -> String
+/// where
+///     T: std::fmt::Debug,
+/// {
+///     SpecializerBorrowedParam::new(Castaway(value), |Castaway(value)| {
+///         format!("{value:?}")
+///     })
+///     .specialize_param(|int: u32| format!("u32 {int}"))
+///     .specialize_param(|flag: bool| (if flag { "yes" } else { "no" }).into())
+///     .run()
+/// }
+///
+/// let borrowed = "borrowed".to_owned();
+///
+/// assert_eq!(describe(3u32), "u32 3");
+/// assert_eq!(describe(true), "yes");
+/// assert_eq!(describe(borrowed.as_str()), "\"borrowed\"");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Castaway<T>(pub T);
+
+impl<T> CastIdentityBorrowed<Castaway<T>> for Castaway<T> {
+    #[inline(always)]
+    fn cast_identity(self) -> Option<Castaway<T>> {
+        Some(self)
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        true
+    }
+}
+
+impl<T, U> CastIdentityBorrowed<U> for Castaway<T>
+where
+    U: LifetimeFree,
+{
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    fn cast_identity(self) -> Option<U> {
+        castaway::cast!(self.0, U).ok()
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        castaway::cast!(None::<T>, Option<U>).is_ok()
+    }
+}
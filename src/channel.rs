@@ -0,0 +1,35 @@
+use futures_channel::{mpsc, oneshot};
+
+use crate::CastIdentityBorrowed;
+
+/// Implement [`CastIdentityBorrowed`] between channel endpoints with
+/// different message types.
+macro_rules! channel_endpoints {
+    ($($module:ident::$endpoint:ident),* $(,)?) => {$(
+        impl<T, U> CastIdentityBorrowed<$module::$endpoint<U>>
+            for $module::$endpoint<T>
+        where
+            T: 'static,
+            U: 'static,
+        {
+            #[cfg_attr(feature = "inline-always", inline(always))]
+            fn cast_identity(self) -> Option<$module::$endpoint<U>> {
+                crate::cast_identity(self)
+            }
+
+            #[inline(always)]
+            fn is_same() -> bool {
+                crate::api::type_eq::<U, T>()
+            }
+        }
+    )*};
+}
+
+channel_endpoints!(
+    mpsc::Sender,
+    mpsc::Receiver,
+    mpsc::UnboundedSender,
+    mpsc::UnboundedReceiver,
+    oneshot::Sender,
+    oneshot::Receiver,
+);
@@ -0,0 +1,162 @@
+use crate::Specializer;
+
+/// Specialized behavior runner requiring at least one arm (Owned -> Owned)
+///
+/// Like [`Specializer`], but [`run()`](CheckedSpecializer::run) is only
+/// available once at least one arm has been added, so that a chain assembled
+/// across helper functions that ends up without any arms (and would always
+/// run the fallback) fails to compile.
+///
+/// ```rust
+/// use specializer::CheckedSpecializer;
+///
+/// fn with_arms<T, F>(
+///     specializer: CheckedSpecializer<T, String, F>,
+/// ) -> CheckedSpecializer<T, String, impl FnOnce(T) -> String, true>
+/// where
+///     T: 'static,
+///     F: FnOnce(T) -> String,
+/// {
+///     specializer
+///         .specialize_param(|int: i32| format!("int {int}"))
+///         .specialize_param(|string: String| string)
+/// }
+///
+/// fn describe<T: 'static>(ty: T) -> String {
+///     with_arms(CheckedSpecializer::new(ty, |_| "unknown".to_owned())).run()
+/// }
+///
+/// assert_eq!(describe(3), "int 3");
+/// assert_eq!(describe("Hello".to_owned()), "Hello");
+/// assert_eq!(describe(()), "unknown");
+/// ```
+#[derive(Debug)]
+pub struct CheckedSpecializer<T, U, F, const SPECIALIZED: bool = false>(
+    Specializer<T, U, F>,
+);
+
+impl<T, U, F> CheckedSpecializer<T, U, F>
+where
+    F: FnOnce(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(params: T, f: F) -> Self {
+        Self(Specializer::new(params, f))
+    }
+}
+
+impl<T, U, F, const SPECIALIZED: bool> CheckedSpecializer<T, U, F, SPECIALIZED>
+where
+    F: FnOnce(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// See [`Specializer::specialize()`].
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        CheckedSpecializer(self.0.specialize(f))
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// See [`Specializer::specialize_map()`].
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        CheckedSpecializer(self.0.specialize_map(p, f, r))
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// See [`Specializer::specialize_param()`].
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(P) -> U,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        P: 'static,
+    {
+        CheckedSpecializer(self.0.specialize_param(f))
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// See [`Specializer::specialize_return()`].
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnOnce(T) -> R,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        R: 'static,
+    {
+        CheckedSpecializer(self.0.specialize_return(f))
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// See [`Specializer::specialize_map_param()`].
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> U,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        P: 'static,
+    {
+        CheckedSpecializer(self.0.specialize_map_param(p, f))
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// See [`Specializer::specialize_map_return()`].
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnOnce(T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> CheckedSpecializer<T, U, impl FnOnce(T) -> U, true>
+    where
+        R: 'static,
+    {
+        CheckedSpecializer(self.0.specialize_map_return(f, r))
+    }
+}
+
+impl<T, U, F> CheckedSpecializer<T, U, F, true>
+where
+    F: FnOnce(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) -> U {
+        self.0.run()
+    }
+}
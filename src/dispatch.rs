@@ -0,0 +1,204 @@
+use alloc::boxed::Box;
+use core::{any::Any, future::Future, pin::Pin};
+
+use crate::{
+    AnySpecializer, AnySpecializerMut, AnySpecializerRef, AsyncSpecializer,
+    AsyncSpecializerBorrowed, AsyncSpecializerBorrowedParam,
+    AsyncSpecializerBorrowedReturn, CastIdentityBorrowed, Specializer,
+    SpecializerBorrowed, SpecializerBorrowedParam, SpecializerBorrowedReturn,
+};
+
+/// Object-safe interface for running any configured specializer
+///
+/// Implemented for every synchronous specializer in this crate, so that
+/// specializers with different parameter types and chains of arms (but the
+/// same return type) can be stored and run uniformly behind `dyn Dispatch<U>`.
+///
+/// ```rust
+/// use specializer::{Dispatch, Specializer, SpecializerBorrowedParam};
+///
+/// fn describe<T: 'static>(ty: T) -> Box<dyn Dispatch<String>> {
+///     Box::new(
+///         Specializer::new(ty, |_| "unknown".to_owned())
+///             .specialize_param(|int: i32| (int * 2).to_string()),
+///     )
+/// }
+///
+/// let mut string = "Hello world".to_owned();
+/// let specializers: Vec<Box<dyn Dispatch<String>>> = [
+///     describe(3),
+///     describe(()),
+///     Box::new(
+///         SpecializerBorrowedParam::new(&mut string, |_| String::new())
+///             .specialize_param(|string: &mut String| string.split_off(5)),
+///     ) as _,
+/// ]
+/// .into();
+/// let results = specializers
+///     .into_iter()
+///     .map(|specializer| specializer.dispatch())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(results, ["6", "unknown", " world"]);
+/// ```
+pub trait Dispatch<U> {
+    /// Run the specializer.
+    fn dispatch(self: Box<Self>) -> U;
+}
+
+/// Object-safe interface for running any configured async specializer
+///
+/// Implemented for every async specializer in this crate; see [`Dispatch`].
+///
+/// ```rust
+/// use specializer::{AsyncDispatch, AsyncSpecializer};
+/// use pasts::Executor;
+///
+/// fn describe<T: 'static>(ty: T) -> Box<dyn AsyncDispatch<String>> {
+///     Box::new(
+///         AsyncSpecializer::new(ty, async |_| "unknown".to_owned())
+///             .specialize_param(async |int: i32| (int * 2).to_string()),
+///     )
+/// }
+///
+/// Executor::default().block_on(async {
+///     assert_eq!(describe(3).dispatch().await, "6");
+///     assert_eq!(describe(()).dispatch().await, "unknown");
+/// });
+/// ```
+pub trait AsyncDispatch<U> {
+    /// Run the specializer.
+    fn dispatch<'a>(self: Box<Self>) -> Pin<Box<dyn Future<Output = U> + 'a>>
+    where
+        Self: 'a;
+}
+
+impl<T, U, F> Dispatch<U> for Specializer<T, U, F>
+where
+    F: FnOnce(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<T, U, F> Dispatch<U> for SpecializerBorrowed<T, U, F>
+where
+    F: FnOnce(T) -> U,
+    T: CastIdentityBorrowed<T>,
+    U: CastIdentityBorrowed<U>,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<T, U, F> Dispatch<U> for SpecializerBorrowedParam<T, U, F>
+where
+    F: FnOnce(T) -> U,
+    T: CastIdentityBorrowed<T>,
+    U: 'static,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<T, U, F> Dispatch<U> for SpecializerBorrowedReturn<T, U, F>
+where
+    F: FnOnce(T) -> U,
+    T: 'static,
+    U: CastIdentityBorrowed<U>,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<U, F> Dispatch<U> for AnySpecializer<U, F>
+where
+    F: FnOnce(Box<dyn Any>) -> U,
+    U: 'static,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<'a, U, F> Dispatch<U> for AnySpecializerRef<'a, U, F>
+where
+    F: FnOnce(&'a dyn Any) -> U,
+    U: 'static,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<'a, U, F> Dispatch<U> for AnySpecializerMut<'a, U, F>
+where
+    F: FnOnce(&'a mut dyn Any) -> U,
+    U: 'static,
+{
+    fn dispatch(self: Box<Self>) -> U {
+        self.run()
+    }
+}
+
+impl<T, U, F> AsyncDispatch<U> for AsyncSpecializer<T, U, F>
+where
+    F: AsyncFnOnce(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    fn dispatch<'a>(self: Box<Self>) -> Pin<Box<dyn Future<Output = U> + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(self.run())
+    }
+}
+
+impl<T, U, F> AsyncDispatch<U> for AsyncSpecializerBorrowed<T, U, F>
+where
+    F: AsyncFnOnce(T) -> U,
+    T: CastIdentityBorrowed<T>,
+    U: CastIdentityBorrowed<U>,
+{
+    fn dispatch<'a>(self: Box<Self>) -> Pin<Box<dyn Future<Output = U> + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(self.run())
+    }
+}
+
+impl<T, U, F> AsyncDispatch<U> for AsyncSpecializerBorrowedParam<T, U, F>
+where
+    F: AsyncFnOnce(T) -> U,
+    T: CastIdentityBorrowed<T>,
+    U: 'static,
+{
+    fn dispatch<'a>(self: Box<Self>) -> Pin<Box<dyn Future<Output = U> + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(self.run())
+    }
+}
+
+impl<T, U, F> AsyncDispatch<U> for AsyncSpecializerBorrowedReturn<T, U, F>
+where
+    F: AsyncFnOnce(T) -> U,
+    T: 'static,
+    U: CastIdentityBorrowed<U>,
+{
+    fn dispatch<'a>(self: Box<Self>) -> Pin<Box<dyn Future<Output = U> + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(self.run())
+    }
+}
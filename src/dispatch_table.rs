@@ -0,0 +1,152 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData};
+
+use crate::{StableType, StableTypeId};
+
+/// Check that a `PhantomData` is of the parameter type, and the handler
+type Arm<U> = (fn(&dyn Any) -> bool, fn(&mut dyn Any) -> U);
+
+/// Build a [`DispatchTable`] from a list of `ParamType => handler` pairs.
+///
+/// Parameter types must implement [`StableType`], and handlers must not
+/// capture their environment (they are coerced to function pointers).  The
+/// table can be built in a `static`, in which case the entries are sorted at
+/// compile time.
+///
+/// # Panics
+///
+/// Panics if the same parameter type is listed more than once (see
+/// [`DispatchTable::new()`]), which fails to compile for a `static` table.
+///
+/// ```rust
+/// use specializer::{DispatchTable, StableType, dispatch_table};
+///
+/// static TABLE: DispatchTable<&str, 3> = dispatch_table![
+///     i32 => |_| "i32",
+///     u8 => |_| "u8",
+///     char => |_| "char",
+/// ];
+///
+/// fn describe<T: StableType>(ty: T) -> &'static str {
+///     TABLE.dispatch(ty).unwrap_or("unknown")
+/// }
+///
+/// assert_eq!(describe(3i32), "i32");
+/// assert_eq!(describe(3u8), "u8");
+/// assert_eq!(describe(()), "unknown");
+/// assert!(TABLE.contains::<char>());
+/// ```
+#[macro_export]
+macro_rules! dispatch_table {
+    ($($param:ty => $f:expr),* $(,)?) => {
+        $crate::DispatchTable::new([$((
+            <$param as $crate::StableType>::STABLE_TYPE_ID,
+            (
+                |ty: &dyn ::core::any::Any| {
+                    ty.is::<::core::marker::PhantomData<$param>>()
+                },
+                |any: &mut dyn ::core::any::Any| {
+                    let f: fn($param) -> _ = $f;
+
+                    f($crate::__take_param::<$param>(any))
+                },
+            ),
+        )),*])
+    };
+}
+
+/// Type-erased boxed parameter, passed to arms in place of `&mut Option<P>`.
+#[cfg(feature = "alloc")]
+pub(crate) struct Boxed(pub(crate) Option<Box<dyn Any>>);
+
+/// Take the parameter out of the type-erased `&mut Option<P>` passed to arms.
+#[doc(hidden)]
+#[inline(always)]
+pub fn __take_param<P>(any: &mut dyn Any) -> P
+where
+    P: 'static,
+{
+    #[cfg(feature = "alloc")]
+    if let Some(Boxed(boxed)) = any.downcast_mut::<Boxed>() {
+        return *boxed.take().unwrap().downcast::<P>().unwrap();
+    }
+
+    any.downcast_mut::<Option<P>>().unwrap().take().unwrap()
+}
+
+/// Allocation-free dispatch table (Owned -> Owned)
+///
+/// Entries are keyed by [`StableTypeId`], which (unlike
+/// [`TypeId`](core::any::TypeId)) is available in const contexts, so the
+/// const constructor sorts them and dispatch is a binary search.  Use the
+/// [`dispatch_table!`](crate::dispatch_table) macro to build one.
+#[derive(Debug)]
+pub struct DispatchTable<U, const N: usize>([(StableTypeId, Arm<U>); N]);
+
+impl<U, const N: usize> DispatchTable<U, N> {
+    /// Create a new dispatch table from a list of entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time, if evaluated in a const context) if more than
+    /// one entry has the same [`StableTypeId`].
+    pub const fn new(entries: [(StableTypeId, Arm<U>); N]) -> Self {
+        let mut entries = entries;
+        let mut i = 1;
+
+        // Insertion sort, since `sort_unstable_by_key()` isn't const
+        while i < N {
+            let mut j = i;
+
+            while j > 0 && entries[j - 1].0.as_u128() > entries[j].0.as_u128() {
+                let entry = entries[j];
+
+                entries[j] = entries[j - 1];
+                entries[j - 1] = entry;
+                j -= 1;
+            }
+            assert!(
+                j == 0 || entries[j - 1].0.as_u128() != entries[j].0.as_u128(),
+                "duplicate dispatch table entry",
+            );
+            i += 1;
+        }
+
+        Self(entries)
+    }
+
+    /// Return true if an entry exists for parameter type `P`.
+    pub fn contains<P>(&self) -> bool
+    where
+        P: StableType,
+    {
+        self.find(P::STABLE_TYPE_ID)
+            .is_some_and(|(accepts, _)| accepts(&PhantomData::<P>))
+    }
+
+    /// Run the entry for the type of `param`.
+    ///
+    /// Returns `Err(param)` if there is no entry for `T`, including when the
+    /// entry for the [`StableTypeId`] of `T` is for another type.
+    #[inline]
+    pub fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: StableType,
+    {
+        let arm = self
+            .find(T::STABLE_TYPE_ID)
+            .filter(|(accepts, _)| accepts(&PhantomData::<T>));
+        let Some((_, run)) = crate::trace::found::<T, _>(arm) else {
+            return Err(param);
+        };
+
+        Ok(run(&mut Some(param)))
+    }
+
+    fn find(&self, id: StableTypeId) -> Option<Arm<U>> {
+        let index = self.0.binary_search_by_key(&id, |(id, _)| *id).ok()?;
+
+        Some(self.0[index].1)
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::boxed::Box;
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+use crate::small_map::SmallMap;
+
+type Arm<P, R> = Box<dyn Fn(P) -> R + Send + Sync>;
+
+/// Runtime registry of specialized behavior keyed by parameter and return
+/// types (Owned -> Owned)
+///
+/// Unlike [`DynSpecializer`](crate::DynSpecializer), which has a single return
+/// type, arms are looked up by both the [`TypeId`] of the parameter and the
+/// [`TypeId`] of the requested return type, so the same parameter type can be
+/// handled differently depending on the output the caller asks for (like
+/// [`Specializer::specialize()`](crate::Specializer::specialize) does
+/// statically).
+///
+/// ```rust
+/// use specializer::DynRegistry;
+///
+/// fn convert<T: 'static, U: 'static + Default>(
+///     registry: &DynRegistry,
+///     ty: T,
+/// ) -> U {
+///     registry.dispatch(ty).unwrap_or_default()
+/// }
+///
+/// let mut registry = DynRegistry::new();
+///
+/// registry
+///     .register(|int: i32| int.to_string())
+///     .register(|int: i32| i64::from(int) * 2);
+///
+/// assert_eq!(convert::<_, String>(&registry, 3), "3");
+/// assert_eq!(convert::<_, i64>(&registry, 3), 6);
+/// assert_eq!(convert::<_, u8>(&registry, 3), 0);
+/// ```
+pub struct DynRegistry {
+    arms: SmallMap<(TypeId, TypeId), Box<dyn Any + Send + Sync>>,
+}
+
+impl DynRegistry {
+    /// Create a new registry without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+        }
+    }
+
+    /// Register an arm for parameter type `P` and return type `R`.
+    ///
+    /// Registering a second arm for the same parameter and return types
+    /// replaces the first.
+    pub fn register<P, R>(
+        &mut self,
+        f: impl Fn(P) -> R + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let arm: Arm<P, R> = Box::new(f);
+
+        self.arms
+            .insert((TypeId::of::<P>(), TypeId::of::<R>()), Box::new(arm));
+        self
+    }
+
+    /// Return true if an arm is registered for parameter type `P` and return
+    /// type `R`.
+    pub fn contains<P, R>(&self) -> bool
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.arms
+            .contains_key(&(TypeId::of::<P>(), TypeId::of::<R>()))
+    }
+
+    /// Return the number of registered arms.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Run the arm registered for the type of `param` and return type `U`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for `T` and `U`.
+    #[inline]
+    pub fn dispatch<T, U>(&self, param: T) -> Result<U, T>
+    where
+        T: 'static,
+        U: 'static,
+    {
+        let arm = self.arms.get(&(TypeId::of::<T>(), TypeId::of::<U>()));
+        let Some(arm) = crate::trace::found::<T, _>(arm) else {
+            return Err(param);
+        };
+
+        Ok(arm.downcast_ref::<Arm<T, U>>().unwrap()(param))
+    }
+}
+
+impl Default for DynRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DynRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynRegistry")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
@@ -0,0 +1,825 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::{
+    any::{self, Any, TypeId},
+    fmt, mem,
+    panic::Location,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, TypeRegistration, TypeRegistry};
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+
+use crate::{
+    ArmInfo, CachedDispatch, RegisterError, RegistrationScope, StaticArm,
+    dispatch_table::Boxed, small_map::SmallMap,
+};
+
+pub(crate) type DynArm<U> = dyn Fn(&mut dyn Any) -> U + Send + Sync;
+
+type Arm<U> = Box<DynArm<U>>;
+
+struct Slot<U> {
+    priority: i32,
+    type_name: &'static str,
+    location: Option<&'static Location<'static>>,
+    arm: Arm<U>,
+}
+
+/// Registration to undo when a [`RegistrationScope`] ends
+struct Undo<U> {
+    type_id: TypeId,
+    priority: i32,
+    /// Arm replaced by the registration
+    replaced: Option<Slot<U>>,
+    /// Type the registered arm's type name previously referred to
+    named: Option<TypeId>,
+}
+
+/// Runtime registry of specialized behavior (Owned -> Owned)
+///
+/// Unlike the builder-style specializers, arms are registered at runtime and
+/// looked up by the [`TypeId`] of the parameter, which allows the set of
+/// specialized types to be open-ended (for example, contributed by plugins).
+///
+/// ```rust
+/// use specializer::DynSpecializer;
+///
+/// fn describe<T: 'static>(spec: &DynSpecializer<String>, ty: T) -> String {
+///     spec.dispatch(ty).unwrap_or_else(|_| "unknown".to_owned())
+/// }
+///
+/// let mut spec = DynSpecializer::new();
+///
+/// spec.register(|int: i32| (int * 2).to_string())
+///     .register(|string: String| string);
+///
+/// assert_eq!(describe(&spec, 3), "6");
+/// assert_eq!(describe(&spec, "Hello world".to_string()), "Hello world");
+/// assert_eq!(describe(&spec, ()), "unknown");
+/// ```
+pub struct DynSpecializer<U> {
+    /// Arms for each type, sorted by ascending (unique) priority
+    arms: SmallMap<TypeId, Vec<Slot<U>>>,
+    /// Parameter types of registered arms, by type name
+    names: BTreeMap<&'static str, TypeId>,
+    /// Changes to undo when the [`RegistrationScope`] ends
+    undo: Vec<Undo<U>>,
+    /// Whether a [`RegistrationScope`] is active (or was leaked)
+    scoped: bool,
+}
+
+impl<U> DynSpecializer<U> {
+    /// Create a new specializer without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+            names: BTreeMap::new(),
+            undo: Vec::new(),
+            scoped: false,
+        }
+    }
+
+    /// Register an arm for parameter type `P` with the default priority (0).
+    ///
+    /// Registering a second arm for the same parameter type and priority
+    /// replaces the first.
+    #[track_caller]
+    pub fn register<P>(
+        &mut self,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+    {
+        self.register_with_priority(0, f)
+    }
+
+    /// Register an arm for parameter type `P` with a priority.
+    ///
+    /// When multiple arms are registered for the same parameter type, the one
+    /// with the highest priority is dispatched to, regardless of registration
+    /// order.  Registering a second arm for the same parameter type and
+    /// priority replaces the first.
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// // Application overrides
+    /// spec.register_with_priority(1, |int: i32| format!("app: {int}"));
+    /// // Library-provided defaults
+    /// spec.register(|int: i32| format!("library: {int}"))
+    ///     .register(|int: u32| format!("library: {int}"));
+    ///
+    /// assert_eq!(spec.dispatch(1i32), Ok("app: 1".to_owned()));
+    /// assert_eq!(spec.dispatch(1u32), Ok("library: 1".to_owned()));
+    /// ```
+    #[track_caller]
+    pub fn register_with_priority<P>(
+        &mut self,
+        priority: i32,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+    {
+        self.register_at(priority, Location::caller(), f)
+    }
+
+    /// Register an arm, recording `location` as where it was registered from.
+    pub(crate) fn register_at<P>(
+        &mut self,
+        priority: i32,
+        location: &'static Location<'static>,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+    {
+        let arm = move |any: &mut dyn Any| -> U { f(crate::__take_param(any)) };
+
+        self.insert(
+            TypeId::of::<P>(),
+            Slot {
+                priority,
+                type_name: any::type_name::<P>(),
+                location: Some(location),
+                arm: Box::new(arm),
+            },
+        );
+        self
+    }
+
+    /// Register an arm for parameter type `P` under an externally provided
+    /// key.
+    ///
+    /// This is useful when the key comes from somewhere other than the arm
+    /// itself (for example, a plugin manifest).  Fails without registering
+    /// anything if `type_id` isn't the [`TypeId`] of `P`, rather than
+    /// mis-dispatching later.
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    /// let key = TypeId::of::<i32>();
+    ///
+    /// assert!(spec.try_register_as(key, |int: i32| int * 2).is_ok());
+    ///
+    /// let error = spec
+    ///     .try_register_as(key, |int: u8| i32::from(int))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.expected_name(), Some("i32"));
+    /// assert_eq!(error.found_name(), "u8");
+    /// assert_eq!(spec.dispatch(3), Ok(6));
+    /// assert_eq!(spec.dispatch(3u8), Err(3));
+    /// ```
+    #[track_caller]
+    pub fn try_register_as<P>(
+        &mut self,
+        type_id: TypeId,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> Result<&mut Self, RegisterError>
+    where
+        P: 'static,
+    {
+        let found = TypeId::of::<P>();
+
+        if type_id != found {
+            let slot = self.arms.get(&type_id).and_then(|slots| slots.first());
+            let expected = slot.map(|slot| slot.type_name);
+
+            return Err(RegisterError::new(
+                type_id,
+                expected,
+                found,
+                any::type_name::<P>(),
+            ));
+        }
+
+        Ok(self.register(f))
+    }
+
+    /// Register each of the statically-constructed `arms`.
+    ///
+    /// This is useful for picking up arms collected into a distributed slice
+    /// (see [`StaticArm`]).
+    ///
+    /// ```rust
+    /// use specializer::{DynSpecializer, StaticArm};
+    ///
+    /// static ARMS: &[StaticArm<String>] =
+    ///     &[specializer::static_arm!(i32 => |int| (int * 2).to_string())];
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register_static(ARMS)
+    ///     .register(|string: String| string);
+    ///
+    /// assert_eq!(spec.dispatch(3), Ok("6".to_owned()));
+    /// assert_eq!(spec.dispatch("Hello".to_owned()), Ok("Hello".to_owned()));
+    /// ```
+    pub fn register_static(&mut self, arms: &[StaticArm<U>]) -> &mut Self
+    where
+        U: 'static,
+    {
+        for arm in arms {
+            self.insert(
+                arm.param_type_id(),
+                Slot {
+                    priority: 0,
+                    type_name: arm.param_type_name(),
+                    location: None,
+                    arm: Box::new(arm.arm()),
+                },
+            );
+        }
+
+        self
+    }
+
+    /// Return true if an arm is registered for parameter type `P`.
+    pub fn contains<P>(&self) -> bool
+    where
+        P: 'static,
+    {
+        self.arms.contains_key(&TypeId::of::<P>())
+    }
+
+    /// Return the number of parameter types with registered arms.
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i8| int.to_string())
+    ///     .register(|int: i16| int.to_string())
+    ///     .register(|int: i32| int.to_string())
+    ///     .register(|int: i64| int.to_string())
+    ///     .register(|int: i128| int.to_string())
+    ///     .register(|int: u8| int.to_string())
+    ///     .register(|int: u16| int.to_string())
+    ///     .register(|int: u32| int.to_string())
+    ///     .register(|int: u64| int.to_string())
+    ///     .register(|int: u128| int.to_string())
+    ///     .register_with_priority(1, |int: u128| (int * 2).to_string());
+    ///
+    /// assert_eq!(spec.len(), 10);
+    /// assert_eq!(spec.dispatch(1i8), Ok("1".to_owned()));
+    /// assert_eq!(spec.dispatch(1u128), Ok("2".to_owned()));
+    /// assert_eq!(spec.dispatch(1f32), Err(1.0));
+    /// ```
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Run the arm registered for the type of `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for `T`.
+    #[inline]
+    pub fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: 'static,
+    {
+        let Some(arm) =
+            crate::trace::found::<T, _>(self.arm(TypeId::of::<T>()))
+        else {
+            return Err(param);
+        };
+
+        Ok(arm(&mut Some(param)))
+    }
+
+    /// Create a dispatcher that caches the most recently matched arm.
+    ///
+    /// This is useful at call sites that repeatedly dispatch values of the
+    /// same type, since a cache hit skips the map lookup.
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// let cached = spec.cached();
+    ///
+    /// for i in 0..4 {
+    ///     assert_eq!(cached.dispatch(i), Ok(i * 2));
+    /// }
+    ///
+    /// assert_eq!(cached.dispatch(3u8), Err(3));
+    /// ```
+    pub fn cached(&self) -> CachedDispatch<'_, U> {
+        CachedDispatch::new(self)
+    }
+
+    pub(crate) fn arm(&self, type_id: TypeId) -> Option<&DynArm<U>> {
+        let slot = self.arms.get(&type_id)?.last()?;
+
+        Some(&*slot.arm)
+    }
+
+    /// Look up the arm for a type-erased parameter, tracing the dispatch with
+    /// the name of the type it was registered for.
+    fn erased_arm(&self, type_id: TypeId) -> Option<&DynArm<U>> {
+        let slot = self.arms.get(&type_id).and_then(|slots| slots.last());
+        let param = slot.map_or(crate::trace::UNKNOWN, |s| s.type_name);
+
+        crate::trace::found_named(param, slot.map(|slot| &*slot.arm))
+    }
+
+    fn insert(&mut self, type_id: TypeId, slot: Slot<U>) {
+        let priority = slot.priority;
+        let named = self.names.insert(slot.type_name, type_id);
+
+        let arms = self.arms.get_or_insert_with(type_id, Vec::new);
+        let replaced =
+            match arms.binary_search_by_key(&priority, |slot| slot.priority) {
+                Ok(index) => Some(mem::replace(&mut arms[index], slot)),
+                Err(index) => {
+                    arms.insert(index, slot);
+                    None
+                }
+            };
+
+        if self.scoped {
+            self.undo.push(Undo {
+                type_id,
+                priority,
+                replaced,
+                named,
+            });
+        }
+    }
+
+    /// Begin a scope.
+    ///
+    /// Scopes can't nest, since the guard borrows the registry mutably until
+    /// it's dropped, so a scope that's still active here was leaked: its
+    /// registrations are kept, and no longer tracked.
+    pub(crate) fn begin_scope(&mut self) {
+        self.undo.clear();
+        self.scoped = true;
+    }
+
+    /// End the scope, undoing every registration made since it began.
+    pub(crate) fn end_scope(&mut self) {
+        for undo in self.undo.drain(..).rev() {
+            let Some(arms) = self.arms.get_mut(&undo.type_id) else {
+                continue;
+            };
+            let Ok(index) =
+                arms.binary_search_by_key(&undo.priority, |slot| slot.priority)
+            else {
+                continue;
+            };
+            let slot = match undo.replaced {
+                Some(replaced) => mem::replace(&mut arms[index], replaced),
+                None => arms.remove(index),
+            };
+
+            if arms.is_empty() {
+                self.arms.remove(&undo.type_id);
+            }
+
+            match undo.named {
+                Some(type_id) => self.names.insert(slot.type_name, type_id),
+                None => self.names.remove(slot.type_name),
+            };
+        }
+
+        self.scoped = false;
+    }
+
+    /// Begin a scope in which registrations are temporary.
+    ///
+    /// All arms registered through the returned guard are removed (and any
+    /// arms they replaced or shadowed restored) when it's dropped.
+    ///
+    /// If the guard is leaked instead (for example, with [`mem::forget()`]),
+    /// its registrations are kept.  Registrations made after that are still
+    /// recorded to be undone (using memory for each one) until the next scope
+    /// begins, which discards the record.
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2).register(|int: u16| i32::from(int));
+    ///
+    /// {
+    ///     let mut scope = spec.scope();
+    ///
+    ///     scope
+    ///         .register(|int: i32| int * 3)
+    ///         .register_with_priority(1, |int: u16| -i32::from(int))
+    ///         .register(|int: u8| i32::from(int));
+    ///
+    ///     assert_eq!(scope.dispatch(3), Ok(9));
+    ///     assert_eq!(scope.dispatch(3u16), Ok(-3));
+    ///     assert_eq!(scope.dispatch(3u8), Ok(3));
+    /// }
+    ///
+    /// assert_eq!(spec.dispatch(3), Ok(6));
+    /// assert_eq!(spec.dispatch(3u16), Ok(3));
+    /// assert_eq!(spec.dispatch(3u8), Err(3));
+    /// assert_eq!(spec.type_id_by_name("u16"), Some(TypeId::of::<u16>()));
+    /// assert_eq!(spec.type_id_by_name("u8"), None);
+    /// ```
+    ///
+    /// A leaked guard doesn't affect the scopes after it:
+    ///
+    /// ```rust
+    /// use std::mem;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    /// let mut scope = spec.scope();
+    ///
+    /// scope.register(|int: i32| int * 2);
+    /// mem::forget(scope);
+    /// spec.scoped(|scope| {
+    ///     scope.register(|int: i32| int * 3);
+    /// });
+    ///
+    /// assert_eq!(spec.dispatch(3), Ok(6));
+    /// ```
+    pub fn scope(&mut self) -> RegistrationScope<'_, U> {
+        RegistrationScope::new(self)
+    }
+
+    /// Run `f` with a scope in which registrations are temporary.
+    ///
+    /// See [`DynSpecializer::scope()`].
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// let tripled = spec.scoped(|spec| {
+    ///     spec.register(|int: i32| int * 3);
+    ///     spec.dispatch(3)
+    /// });
+    ///
+    /// assert_eq!(tripled, Ok(9));
+    /// assert_eq!(spec.dispatch(3), Err(3));
+    /// ```
+    ///
+    /// The registry can't be replaced (or otherwise changed in ways that
+    /// couldn't be undone) from inside the scope:
+    ///
+    /// ```rust,compile_fail
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::<i32>::new();
+    ///
+    /// spec.scoped(|scope| **scope = DynSpecializer::new());
+    /// ```
+    pub fn scoped<R>(
+        &mut self,
+        f: impl FnOnce(&mut RegistrationScope<'_, U>) -> R,
+    ) -> R {
+        f(&mut self.scope())
+    }
+
+    /// Iterate over information about each registered arm.
+    ///
+    /// Arms are grouped by parameter type (in an unspecified order), and
+    /// within each group are ordered from highest to lowest priority, so the
+    /// first arm of each group is the one that's dispatched to.
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2)
+    ///     .register_with_priority(1, |int: i32| int * 3);
+    ///
+    /// let arms = spec.arms().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(arms.len(), 2);
+    /// assert_eq!(arms[0].param_type_name(), "i32");
+    /// assert_eq!(arms[0].priority(), 1);
+    /// assert!(arms[0].is_active());
+    /// assert_eq!(arms[1].priority(), 0);
+    /// assert!(!arms[1].is_active());
+    ///
+    /// for arm in spec.arms() {
+    ///     println!("{arm}");
+    /// }
+    /// ```
+    pub fn arms(&self) -> impl Iterator<Item = ArmInfo> + '_ {
+        self.arms.iter().flat_map(|(&type_id, arms)| {
+            arms.iter().rev().enumerate().map(move |(index, slot)| {
+                ArmInfo::new(
+                    type_id,
+                    slot.type_name,
+                    slot.priority,
+                    slot.location,
+                    index == 0,
+                )
+            })
+        })
+    }
+
+    /// Look up the parameter type of a registered arm by its type name.
+    ///
+    /// Type names are those returned by [`core::any::type_name()`], which
+    /// comes with some caveats: the exact output isn't guaranteed to be stable
+    /// across compiler versions, and distinct types (such as the same type
+    /// from two versions of a crate) may share a name, in which case the most
+    /// recently registered one is returned.  This is intended for selecting
+    /// arms from configuration files or scripting layers, not as a stable
+    /// identifier.
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// assert_eq!(spec.type_id_by_name("i32"), Some(TypeId::of::<i32>()));
+    /// assert_eq!(spec.type_id_by_name("u8"), None);
+    /// ```
+    pub fn type_id_by_name(&self, type_name: &str) -> Option<TypeId> {
+        self.names.get(type_name).copied()
+    }
+
+    /// Run the arm registered under `type_name` with the boxed `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered under `type_name`, or if
+    /// the boxed value isn't of that type.  See
+    /// [`DynSpecializer::type_id_by_name()`] for caveats about type names.
+    ///
+    /// ```rust
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// assert_eq!(spec.dispatch_named("i32", Box::new(3)).ok(), Some(6));
+    /// assert!(spec.dispatch_named("i32", Box::new(3u8)).is_err());
+    /// assert!(spec.dispatch_named("u8", Box::new(3u8)).is_err());
+    /// ```
+    #[inline]
+    pub fn dispatch_named(
+        &self,
+        type_name: &str,
+        param: Box<dyn Any>,
+    ) -> Result<U, Box<dyn Any>> {
+        let Some(type_id) = self
+            .type_id_by_name(type_name)
+            .filter(|&type_id| type_id == (*param).type_id())
+        else {
+            return Err(param);
+        };
+        let Some(arm) = self.erased_arm(type_id) else {
+            return Err(param);
+        };
+
+        Ok(arm(&mut Boxed(Some(param))))
+    }
+
+    /// Run the arm registered for the runtime type of the boxed `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for the boxed type.
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// assert_eq!(spec.dispatch_boxed(Box::new(3)).ok(), Some(6));
+    /// assert!(spec.dispatch_boxed(Box::new(3u8)).is_err());
+    /// ```
+    #[inline]
+    pub fn dispatch_boxed(
+        &self,
+        param: Box<dyn Any>,
+    ) -> Result<U, Box<dyn Any>> {
+        let Some(arm) = self.erased_arm((*param).type_id()) else {
+            return Err(param);
+        };
+
+        Ok(arm(&mut Boxed(Some(param))))
+    }
+
+    /// Run the arm registered for the reflected type of the boxed `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for the reflected type.
+    ///
+    /// ```rust
+    /// use bevy_reflect::Reflect;
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// let value: Box<dyn Reflect> = Box::new(3i32);
+    ///
+    /// assert_eq!(spec.dispatch_reflect(value).ok(), Some(6));
+    /// assert!(spec.dispatch_reflect(Box::new(3u8)).is_err());
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    #[inline]
+    pub fn dispatch_reflect(
+        &self,
+        param: Box<dyn Reflect>,
+    ) -> Result<U, Box<dyn Reflect>> {
+        let Some(arm) = self.erased_arm(Any::type_id(param.as_any())) else {
+            return Err(param);
+        };
+
+        Ok(arm(&mut Boxed(Some(param.into_any()))))
+    }
+
+    /// Iterate over the registrations in `registry` with a registered arm.
+    ///
+    /// ```rust
+    /// use bevy_reflect::TypeRegistry;
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// let registry = TypeRegistry::new();
+    /// let paths = spec
+    ///     .registrations(&registry)
+    ///     .map(|registration| registration.type_info().type_path())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(paths, ["i32"]);
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    pub fn registrations<'a>(
+        &'a self,
+        registry: &'a TypeRegistry,
+    ) -> impl Iterator<Item = &'a TypeRegistration> + 'a {
+        registry.iter().filter(|registration| {
+            self.arms
+                .contains_key(&TypeRegistration::type_id(registration))
+        })
+    }
+
+    /// Run the matching arm for each of the type-erased `params`.
+    ///
+    /// Each result is `None` if no arm is registered for that element's type,
+    /// in which case the element is left in place.  Elements that are passed
+    /// to an arm are replaced with `Box::new(())` (which doesn't allocate).
+    /// For collections that should only be borrowed, see
+    /// [`AnySpecializerRef`](crate::AnySpecializerRef) and
+    /// [`AnySpecializerMut`](crate::AnySpecializerMut).
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| (int * 2).to_string())
+    ///     .register(|string: String| string);
+    ///
+    /// let mut values: Vec<Box<dyn Any>> = [
+    ///     Box::new(3) as _,
+    ///     Box::new(()) as _,
+    ///     Box::new("Hi".to_string()) as _,
+    /// ]
+    /// .into();
+    /// let results = spec.dispatch_each(&mut values).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(results, [Some("6".into()), None, Some("Hi".into())]);
+    /// assert!(values.iter().all(|value| value.is::<()>()));
+    /// ```
+    #[inline]
+    pub fn dispatch_each(
+        &self,
+        params: &mut [Box<dyn Any>],
+    ) -> impl Iterator<Item = Option<U>> {
+        params.iter_mut().map(|param| {
+            let arm = self.erased_arm((**param).type_id())?;
+            let param = mem::replace(param, Box::new(()));
+
+            Some(arm(&mut Boxed(Some(param))))
+        })
+    }
+
+    /// Run the matching arm for each of `params` in parallel.
+    ///
+    /// Each result is `Err(param)` if no arm is registered for `T`.
+    ///
+    /// ```rust
+    /// use rayon::prelude::*;
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| int * 2);
+    ///
+    /// let results = spec
+    ///     .par_dispatch((0..1000).into_par_iter())
+    ///     .map(Result::unwrap)
+    ///     .sum::<i32>();
+    ///
+    /// assert_eq!(results, 999 * 1000);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_dispatch<T, I>(
+        &self,
+        params: I,
+    ) -> impl ParallelIterator<Item = Result<U, T>>
+    where
+        T: Send + 'static,
+        U: Send,
+        I: IntoParallelIterator<Item = T>,
+    {
+        params.into_par_iter().map(|param| self.dispatch(param))
+    }
+
+    /// Run the matching arm for each of the type-erased `params` in parallel.
+    ///
+    /// Parallel version of [`dispatch_each()`](Self::dispatch_each).
+    ///
+    /// ```rust
+    /// use std::any::Any;
+    ///
+    /// use rayon::prelude::*;
+    /// use specializer::DynSpecializer;
+    ///
+    /// let mut spec = DynSpecializer::new();
+    ///
+    /// spec.register(|int: i32| (int * 2).to_string())
+    ///     .register(|string: String| string);
+    ///
+    /// let mut values: Vec<Box<dyn Any + Send>> = [
+    ///     Box::new(3) as _,
+    ///     Box::new(()) as _,
+    ///     Box::new("Hi".to_string()) as _,
+    /// ]
+    /// .into();
+    /// let results = spec.par_dispatch_each(&mut values).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(results, [Some("6".into()), None, Some("Hi".into())]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_dispatch_each(
+        &self,
+        params: &mut [Box<dyn Any + Send>],
+    ) -> impl ParallelIterator<Item = Option<U>>
+    where
+        U: Send,
+    {
+        params.par_iter_mut().map(|param| {
+            let arm = self.erased_arm((**param).type_id())?;
+            let param = mem::replace(param, Box::new(()));
+            let param: Box<dyn Any> = param;
+
+            Some(arm(&mut Boxed(Some(param))))
+        })
+    }
+}
+
+impl<U> Default for DynSpecializer<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> fmt::Debug for DynSpecializer<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynSpecializer")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
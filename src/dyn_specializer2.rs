@@ -0,0 +1,122 @@
+use alloc::boxed::Box;
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+use crate::small_map::SmallMap;
+
+type Arm<U> = Box<dyn Fn(&dyn Any, &dyn Any) -> U + Send + Sync>;
+
+/// Runtime registry of double-dispatched behavior (Type-erased pair -> Owned)
+///
+/// Arms are looked up by the pair of runtime types of both parameters, which
+/// covers cases like collision handling or binary operators that can't be
+/// expressed by dispatching on a single parameter.
+///
+/// ```rust
+/// use std::any::Any;
+///
+/// use specializer::DynSpecializer2;
+///
+/// struct Ship;
+/// struct Asteroid;
+///
+/// let mut collide = DynSpecializer2::new();
+///
+/// collide
+///     .register(|_: &Ship, _: &Ship| "ships bounce")
+///     .register(|_: &Ship, _: &Asteroid| "ship explodes")
+///     .register(|_: &Asteroid, _: &Ship| "ship explodes");
+///
+/// let objects: [&dyn Any; 3] = [&Ship, &Asteroid, &Ship];
+///
+/// assert_eq!(collide.dispatch(objects[0], objects[2]), Some("ships bounce"));
+/// assert_eq!(collide.dispatch(objects[1], objects[0]), Some("ship explodes"));
+/// assert_eq!(collide.dispatch(objects[1], objects[1]), None);
+/// ```
+pub struct DynSpecializer2<U> {
+    /// Arms, with the name of their pair of parameter types
+    arms: SmallMap<(TypeId, TypeId), (&'static str, Arm<U>)>,
+}
+
+impl<U> DynSpecializer2<U> {
+    /// Create a new specializer without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+        }
+    }
+
+    /// Register an arm for the parameter types `A` and `B`.
+    ///
+    /// Registering a second arm for the same pair of parameter types replaces
+    /// the first.
+    pub fn register<A, B>(
+        &mut self,
+        f: impl Fn(&A, &B) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        A: 'static,
+        B: 'static,
+    {
+        let arm = move |a: &dyn Any, b: &dyn Any| -> U {
+            f(a.downcast_ref().unwrap(), b.downcast_ref().unwrap())
+        };
+
+        let name = core::any::type_name::<(A, B)>();
+
+        self.arms.insert(
+            (TypeId::of::<A>(), TypeId::of::<B>()),
+            (name, Box::new(arm)),
+        );
+        self
+    }
+
+    /// Return true if an arm is registered for parameter types `A` and `B`.
+    pub fn contains<A, B>(&self) -> bool
+    where
+        A: 'static,
+        B: 'static,
+    {
+        self.arms
+            .contains_key(&(TypeId::of::<A>(), TypeId::of::<B>()))
+    }
+
+    /// Return the number of registered arms.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Run the arm registered for the runtime types of `a` and `b`.
+    ///
+    /// Returns `None` if no arm is registered for the pair of types.
+    #[inline]
+    pub fn dispatch(&self, a: &dyn Any, b: &dyn Any) -> Option<U> {
+        let arm = self.arms.get(&(Any::type_id(a), Any::type_id(b)));
+        let name = arm.map_or(crate::trace::UNKNOWN, |(name, _)| name);
+        let (_, arm) = crate::trace::found_named(name, arm)?;
+
+        Some(arm(a, b))
+    }
+}
+
+impl<U> Default for DynSpecializer2<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> fmt::Debug for DynSpecializer2<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynSpecializer2")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::fmt::{self, Display, Write};
+
+/// Integer types formatted with [`itoa`](https://docs.rs/itoa)
+#[cfg(feature = "itoa")]
+macro_rules! integers {
+    ($m:ident!($($args:tt)*)) => {
+        $m!(
+            $($args)*;
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+        )
+    };
+}
+
+/// Return early with `$f` applied to the formatted integer, if `$value` is
+/// one.
+#[cfg(feature = "itoa")]
+macro_rules! format_integer {
+    ($value:expr, $f:expr; $($ty:ty),*) => {$(
+        if let Some(&int) = crate::cast_identity_ref::<_, $ty>($value) {
+            return $f(itoa::Buffer::new().format(int));
+        }
+    )*};
+}
+
+/// Write `value` to `writer`, taking fast paths for string and integer types.
+///
+/// String types (`&'static str`, `String`, `Box<str>`, and
+/// `Cow<'static, str>`) are written with a single
+/// [`write_str()`](Write::write_str), bypassing the formatting machinery,
+/// and with the `itoa` feature integers are formatted with
+/// [`itoa`](https://docs.rs/itoa).  Other types use their [`Display`]
+/// implementation.
+///
+/// ```rust
+/// use core::fmt::Display;
+///
+/// fn show<T: Display + 'static>(value: &T) -> String {
+///     let mut string = String::new();
+///
+///     specializer::write_display_fast(&mut string, value).unwrap();
+///     string
+/// }
+///
+/// assert_eq!(show(&"Hello"), "Hello");
+/// assert_eq!(show(&-42i64), "-42");
+/// assert_eq!(show(&1.5f32), "1.5");
+/// ```
+pub fn write_display_fast<T, W>(writer: &mut W, value: &T) -> fmt::Result
+where
+    T: Display + 'static,
+    W: Write + ?Sized,
+{
+    if let Some(string) = as_str(value) {
+        return writer.write_str(string);
+    }
+
+    #[cfg(feature = "itoa")]
+    integers!(format_integer!(value, |int| writer.write_str(int)));
+
+    write!(writer, "{value}")
+}
+
+/// Return `value` as a string, borrowing instead of allocating when possible.
+///
+/// String types are borrowed; with the `itoa` feature integers are formatted
+/// with [`itoa`](https://docs.rs/itoa); other types are formatted with their
+/// [`Display`] implementation.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use specializer::display_str;
+///
+/// let string = "Hello".to_owned();
+///
+/// assert!(matches!(display_str(&string), Cow::Borrowed("Hello")));
+/// assert_eq!(display_str(&42u8), "42");
+/// assert_eq!(display_str(&'c'), "c");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn display_str<T>(value: &T) -> Cow<'_, str>
+where
+    T: Display + 'static,
+{
+    if let Some(string) = as_str(value) {
+        return Cow::Borrowed(string);
+    }
+
+    #[cfg(feature = "itoa")]
+    integers!(format_integer!(value, |int| Cow::Owned(String::from(int))));
+
+    Cow::Owned(value.to_string())
+}
+
+/// Convert `value` into a [`String`], reusing its allocation when possible.
+///
+/// `String`, `Box<str>`, and owned `Cow<'static, str>` values are converted
+/// without reallocating; other types are formatted like with
+/// [`display_str()`].
+///
+/// ```rust
+/// let string = "Hello".to_owned();
+/// let ptr = string.as_ptr();
+/// let string = specializer::into_string_fast(string);
+///
+/// assert_eq!(string.as_ptr(), ptr);
+/// assert_eq!(specializer::into_string_fast(-7i32), "-7");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn into_string_fast<T>(value: T) -> String
+where
+    T: Display + 'static,
+{
+    if crate::api::type_eq::<T, String>() {
+        return crate::cast_identity(value).unwrap();
+    }
+
+    if crate::api::type_eq::<T, Box<str>>() {
+        return crate::cast_identity::<T, Box<str>>(value).unwrap().into();
+    }
+
+    if crate::api::type_eq::<T, Cow<'static, str>>() {
+        return crate::cast_identity::<T, Cow<'static, str>>(value)
+            .unwrap()
+            .into_owned();
+    }
+
+    display_str(&value).into_owned()
+}
+
+/// Return `value` as a `&str` if it's a string type.
+fn as_str<T>(value: &T) -> Option<&str>
+where
+    T: 'static,
+{
+    if let Some(string) = crate::cast_identity_ref::<T, &str>(value) {
+        return Some(string);
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        if let Some(string) = crate::cast_identity_ref::<T, String>(value) {
+            return Some(string);
+        }
+
+        if let Some(string) = crate::cast_identity_ref::<T, Box<str>>(value) {
+            return Some(string);
+        }
+
+        if let Some(string) =
+            crate::cast_identity_ref::<T, Cow<'static, str>>(value)
+        {
+            return Some(string);
+        }
+    }
+
+    None
+}
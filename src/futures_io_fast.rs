@@ -0,0 +1,209 @@
+use std::{
+    future,
+    io::{self, IoSlice},
+    pin::Pin,
+    task::Poll,
+    vec::Vec,
+};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// Async version of [`read_to_end_fast()`](crate::read_to_end_fast) for
+/// [`AsyncRead`]ers.
+///
+/// In-memory readers (see [`remaining_bytes()`](crate::remaining_bytes)) are
+/// appended to `buf` with a single copy, without polling.  Reads failing with
+/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted) are retried.
+///
+/// ```rust
+/// use pasts::Executor;
+///
+/// Executor::default().block_on(async {
+///     let mut reader: &'static [u8] = b"Hello world";
+///     let mut buf = Vec::new();
+///     let len = specializer::read_to_end_fast_async(&mut reader, &mut buf)
+///         .await
+///         .unwrap();
+///
+///     assert_eq!(len, 11);
+///     assert_eq!(buf, b"Hello world");
+/// });
+/// ```
+pub async fn read_to_end_fast_async<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<usize>
+where
+    R: AsyncRead + Unpin + 'static,
+{
+    let append = |bytes: &[u8]| {
+        buf.extend_from_slice(bytes);
+        bytes.len()
+    };
+
+    if let Ok(len) = crate::dispatch_read(reader, append) {
+        return Ok(len);
+    }
+
+    let start = buf.len();
+    let mut chunk = [0; 8192];
+
+    loop {
+        let len = read(reader, &mut chunk).await?;
+
+        if len == 0 {
+            return Ok(buf.len() - start);
+        }
+
+        buf.extend_from_slice(&chunk[..len]);
+    }
+}
+
+/// Async version of [`copy_fast()`](crate::copy_fast) for [`AsyncRead`]
+/// readers and [`AsyncWrite`] writers.
+///
+/// In-memory readers (see [`remaining_bytes()`](crate::remaining_bytes)) are
+/// written without an intermediate buffer, and appended directly (without
+/// polling) to `Vec<u8>` writers.  Reads and writes failing with
+/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted) are retried.
+///
+/// ```rust
+/// use pasts::Executor;
+///
+/// Executor::default().block_on(async {
+///     let mut reader: &'static [u8] = b"Hello world";
+///     let mut writer = Vec::new();
+///     let len = specializer::copy_fast_async(&mut reader, &mut writer)
+///         .await
+///         .unwrap();
+///
+///     assert_eq!(len, 11);
+///     assert_eq!(writer, b"Hello world");
+/// });
+/// ```
+pub async fn copy_fast_async<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<u64>
+where
+    R: AsyncRead + Unpin + 'static,
+    W: AsyncWrite + Unpin + 'static,
+{
+    if let Some(bytes) = crate::remaining_bytes(reader) {
+        let len = bytes.len();
+        let append = |vec: &mut Vec<u8>| vec.extend_from_slice(bytes);
+
+        if crate::dispatch_write(writer, append).is_err() {
+            write_all(writer, bytes).await?;
+        }
+
+        crate::io_fast::consume(reader, len);
+        return Ok(len as u64);
+    }
+
+    let mut total = 0;
+    let mut chunk = [0; 8192];
+
+    loop {
+        let len = read(reader, &mut chunk).await?;
+
+        if len == 0 {
+            return Ok(total);
+        }
+
+        write_all(writer, &chunk[..len]).await?;
+        total += len as u64;
+    }
+}
+
+/// Write the concatenation of `bufs`, taking fast paths for known writers.
+///
+/// `Vec<u8>` writers have every buffer appended directly (reserving once),
+/// without polling; other writers use
+/// [`AsyncWrite::poll_write_vectored()`], and may write only part of the
+/// data, like a single vectored write.
+///
+/// ```rust
+/// use std::io::IoSlice;
+///
+/// use pasts::Executor;
+///
+/// Executor::default().block_on(async {
+///     let mut writer = Vec::new();
+///     let bufs = [IoSlice::new(b"Hello"), IoSlice::new(b" world")];
+///     let len = specializer::write_vectored_fast_async(&mut writer, &bufs)
+///         .await
+///         .unwrap();
+///
+///     assert_eq!(len, 11);
+///     assert_eq!(writer, b"Hello world");
+/// });
+/// ```
+pub async fn write_vectored_fast_async<W>(
+    writer: &mut W,
+    bufs: &[IoSlice<'_>],
+) -> io::Result<usize>
+where
+    W: AsyncWrite + Unpin + 'static,
+{
+    let append = |vec: &mut Vec<u8>| {
+        let len = bufs.iter().map(|buf| buf.len()).sum();
+
+        vec.reserve(len);
+        for buf in bufs {
+            vec.extend_from_slice(buf);
+        }
+
+        len
+    };
+
+    if let Ok(len) = crate::dispatch_write(writer, append) {
+        return Ok(len);
+    }
+
+    future::poll_fn(|cx| {
+        retry(|| Pin::new(&mut *writer).poll_write_vectored(cx, bufs))
+    })
+    .await
+}
+
+async fn read<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: AsyncRead + Unpin,
+{
+    future::poll_fn(|cx| retry(|| Pin::new(&mut *reader).poll_read(cx, buf)))
+        .await
+}
+
+async fn write_all<W>(writer: &mut W, mut buf: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    while !buf.is_empty() {
+        let len = future::poll_fn(|cx| {
+            retry(|| Pin::new(&mut *writer).poll_write(cx, buf))
+        })
+        .await?;
+
+        if len == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+
+        buf = &buf[len..];
+    }
+
+    Ok(())
+}
+
+/// Poll an IO operation with `poll` again while it fails with
+/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted).
+fn retry<T>(
+    mut poll: impl FnMut() -> Poll<io::Result<T>>,
+) -> Poll<io::Result<T>> {
+    loop {
+        match poll() {
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+            poll => return poll,
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+use core::{
+    any::{Any, TypeId},
+    panic::Location,
+};
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+use crate::DynSpecializer;
+
+type Registry = BTreeMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+/// One `DynSpecializer<U>` for each return type `U`, keyed by `TypeId` of `U`
+#[cfg(feature = "std")]
+static REGISTRY: RwLock<Registry> = RwLock::new(BTreeMap::new());
+
+/// One `DynSpecializer<U>` for each return type `U`, keyed by `TypeId` of `U`
+///
+/// Without `std`, the registry is protected by a critical section instead.
+#[cfg(not(feature = "std"))]
+static REGISTRY: critical_section::Mutex<RefCell<Registry>> =
+    critical_section::Mutex::new(RefCell::new(BTreeMap::new()));
+
+/// Run `f` with exclusive access to the registry.
+fn write<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    #[cfg(feature = "std")]
+    return f(&mut REGISTRY.write().unwrap_or_else(PoisonError::into_inner));
+
+    #[cfg(not(feature = "std"))]
+    critical_section::with(|cs| f(&mut REGISTRY.borrow_ref_mut(cs)))
+}
+
+/// Run `f` with shared access to the registry.
+fn read<R>(f: impl FnOnce(&Registry) -> R) -> R {
+    #[cfg(feature = "std")]
+    return f(&REGISTRY.read().unwrap_or_else(PoisonError::into_inner));
+
+    #[cfg(not(feature = "std"))]
+    critical_section::with(|cs| f(&REGISTRY.borrow_ref(cs)))
+}
+
+/// Register an arm for parameter type `P` in the global registry.
+///
+/// The global registry holds a separate [`DynSpecializer`] for each return
+/// type `U`.  See [`dispatch_global()`] for an example.
+#[track_caller]
+pub fn register_global<P, U>(f: impl Fn(P) -> U + Send + Sync + 'static)
+where
+    P: 'static,
+    U: 'static,
+{
+    register_global_with_priority(0, f)
+}
+
+/// Register an arm for parameter type `P` in the global registry with a
+/// priority.
+///
+/// See [`DynSpecializer::register_with_priority()`].
+#[track_caller]
+pub fn register_global_with_priority<P, U>(
+    priority: i32,
+    f: impl Fn(P) -> U + Send + Sync + 'static,
+) where
+    P: 'static,
+    U: 'static,
+{
+    let location = Location::caller();
+
+    write(|registry| {
+        registry
+            .entry(TypeId::of::<U>())
+            .or_insert_with(|| Box::new(DynSpecializer::<U>::new()))
+            .downcast_mut::<DynSpecializer<U>>()
+            .unwrap()
+            .register_at(priority, location, f);
+    })
+}
+
+/// Run the arm in the global registry for the type of `param` that returns
+/// `U`.
+///
+/// Returns `Err(param)` if no arm is registered for `T` and `U`.
+///
+/// The registry is locked for reading while the arm runs, so arms must not
+/// register new arms in the global registry.  Without the `std` feature, the
+/// arm runs inside a critical section.
+///
+/// ```rust
+/// fn describe<T: 'static>(ty: T) -> String {
+///     specializer::dispatch_global(ty).unwrap_or_else(|_| "unknown".into())
+/// }
+///
+/// specializer::register_global(|int: i32| (int * 2).to_string());
+/// specializer::register_global(|string: String| string);
+///
+/// assert_eq!(describe(3), "6");
+/// assert_eq!(describe("Hello world".to_string()), "Hello world");
+/// assert_eq!(describe(()), "unknown");
+/// ```
+///
+/// Consulted as the fallback of a [`Specializer`](crate::Specializer), the
+/// registry lets downstream crates specialize a generic function on their own
+/// types, while the function's crate keeps the arms for types it knows about:
+///
+/// ```rust
+/// use specializer::Specializer;
+///
+/// fn describe<T: 'static>(ty: T) -> String {
+///     let fallback = |ty| {
+///         let global = specializer::dispatch_global(ty);
+///
+///         global.unwrap_or_else(|_| "unknown".into())
+///     };
+///
+///     Specializer::new(ty, fallback)
+///         .specialize_param(|int: i32| format!("int {int}"))
+///         .run()
+/// }
+///
+/// // In a downstream crate
+/// struct Meters(f32);
+///
+/// specializer::register_global(|Meters(m): Meters| format!("{m} meters"));
+///
+/// assert_eq!(describe(3), "int 3");
+/// assert_eq!(describe(Meters(1.5)), "1.5 meters");
+/// assert_eq!(describe(()), "unknown");
+/// ```
+#[inline]
+pub fn dispatch_global<T, U>(param: T) -> Result<U, T>
+where
+    T: 'static,
+    U: 'static,
+{
+    read(|registry| {
+        let Some(spec) = registry.get(&TypeId::of::<U>()) else {
+            crate::trace::found::<T, ()>(None);
+
+            return Err(param);
+        };
+
+        spec.downcast_ref::<DynSpecializer<U>>()
+            .unwrap()
+            .dispatch(param)
+    })
+}
@@ -0,0 +1,274 @@
+use std::{
+    boxed::Box,
+    io::{self, BufRead, Cursor, Read, Write},
+    vec::Vec,
+};
+
+/// Return the unread bytes of an in-memory reader without copying.
+///
+/// Recognizes `&'static [u8]`, and [`Cursor`]s over `Vec<u8>`, `Box<[u8]>`,
+/// and `&'static [u8]`.  Returns `None` for any other reader.
+///
+/// ```rust
+/// use std::io::{Cursor, Read};
+///
+/// let mut cursor = Cursor::new(b"Hello world".to_vec());
+///
+/// cursor.read_exact(&mut [0; 6]).unwrap();
+///
+/// assert_eq!(specializer::remaining_bytes(&cursor), Some(&b"world"[..]));
+/// assert_eq!(specializer::remaining_bytes(&std::io::empty()), None);
+/// ```
+pub fn remaining_bytes<R>(reader: &R) -> Option<&[u8]>
+where
+    R: 'static,
+{
+    if let Some(slice) = crate::cast_identity_ref::<R, &[u8]>(reader) {
+        return Some(slice);
+    }
+
+    if let Some(cursor) = crate::cast_identity_ref::<R, Cursor<Vec<u8>>>(reader)
+    {
+        return Some(cursor_remaining(cursor));
+    }
+
+    if let Some(cursor) =
+        crate::cast_identity_ref::<R, Cursor<Box<[u8]>>>(reader)
+    {
+        return Some(cursor_remaining(cursor));
+    }
+
+    if let Some(cursor) = crate::cast_identity_ref::<R, Cursor<&[u8]>>(reader) {
+        return Some(cursor_remaining(cursor));
+    }
+
+    None
+}
+
+/// Arm run for in-memory readers
+///
+/// Implemented for closures taking the unread bytes, so IO wrappers can reuse
+/// the fast path with [`dispatch_read()`], or implement their own arm.
+///
+/// ```rust
+/// use std::io::{Cursor, Read};
+///
+/// fn checksum<R: Read + 'static>(reader: &mut R) -> u32 {
+///     let sum = |bytes: &[u8]| bytes.iter().copied().map(u32::from).sum();
+///
+///     specializer::dispatch_read(reader, sum).unwrap_or_else(|sum| {
+///         let mut bytes = Vec::new();
+///
+///         reader.read_to_end(&mut bytes).unwrap();
+///         sum(&bytes)
+///     })
+/// }
+///
+/// assert_eq!(checksum(&mut Cursor::new(Vec::from([1u8, 2, 3]))), 6);
+/// assert_eq!(checksum(&mut std::io::repeat(1).take(3)), 3);
+/// ```
+pub trait ReadArms<U>: Sized {
+    /// Run the arm with the unread bytes of an in-memory reader.
+    fn bytes(self, bytes: &[u8]) -> U;
+}
+
+impl<U, F> ReadArms<U> for F
+where
+    F: FnOnce(&[u8]) -> U,
+{
+    #[inline(always)]
+    fn bytes(self, bytes: &[u8]) -> U {
+        self(bytes)
+    }
+}
+
+/// Arm run for in-memory writers
+///
+/// Implemented for closures taking the `Vec<u8>` writer, so IO wrappers can
+/// reuse the fast path with [`dispatch_write()`], or implement their own arm.
+///
+/// ```rust
+/// use std::io::Write;
+///
+/// fn write_twice<W: Write + 'static>(writer: &mut W, bytes: &[u8]) {
+///     let append = |vec: &mut Vec<u8>| {
+///         vec.reserve(bytes.len() * 2);
+///         vec.extend_from_slice(bytes);
+///         vec.extend_from_slice(bytes);
+///     };
+///
+///     if specializer::dispatch_write(writer, append).is_err() {
+///         writer.write_all(bytes).unwrap();
+///         writer.write_all(bytes).unwrap();
+///     }
+/// }
+///
+/// let mut vec = Vec::new();
+/// let mut cursor = std::io::Cursor::new(Vec::new());
+///
+/// write_twice(&mut vec, b"ab");
+/// write_twice(&mut cursor, b"ab");
+///
+/// assert_eq!(vec, b"abab");
+/// assert_eq!(cursor.into_inner(), b"abab");
+/// ```
+pub trait WriteArms<U>: Sized {
+    /// Run the arm with a `Vec<u8>` writer.
+    fn vec(self, vec: &mut Vec<u8>) -> U;
+}
+
+impl<U, F> WriteArms<U> for F
+where
+    F: FnOnce(&mut Vec<u8>) -> U,
+{
+    #[inline(always)]
+    fn vec(self, vec: &mut Vec<u8>) -> U {
+        self(vec)
+    }
+}
+
+/// Run the arm of `arms` with the unread bytes of `reader` if it's an
+/// in-memory reader, marking them all as read.
+///
+/// In-memory readers are those recognized by [`remaining_bytes()`].  Returns
+/// `Err(arms)` for all other readers.
+#[inline]
+pub fn dispatch_read<R, U, A>(reader: &mut R, arms: A) -> Result<U, A>
+where
+    R: 'static,
+    A: ReadArms<U>,
+{
+    let Some(bytes) = remaining_bytes(reader) else {
+        return Err(arms);
+    };
+    let len = bytes.len();
+    let output = arms.bytes(bytes);
+
+    consume(reader, len);
+    Ok(output)
+}
+
+/// Run the arm of `arms` with `writer` if it's a `Vec<u8>`.
+///
+/// Returns `Err(arms)` for all other writers.
+#[inline]
+pub fn dispatch_write<W, U, A>(writer: &mut W, arms: A) -> Result<U, A>
+where
+    W: 'static,
+    A: WriteArms<U>,
+{
+    match crate::cast_identity_mut::<W, Vec<u8>>(writer) {
+        Some(vec) => Ok(arms.vec(vec)),
+        None => Err(arms),
+    }
+}
+
+/// Read all bytes until EOF into `buf`, taking fast paths for in-memory
+/// readers.
+///
+/// Readers recognized by [`remaining_bytes()`] are appended to `buf` with a
+/// single copy (and reservation); other readers use [`Read::read_to_end()`],
+/// which retries reads failing with
+/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted).
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// let mut cursor = Cursor::new(b"Hello world".to_vec());
+/// let mut buf = b"> ".to_vec();
+///
+/// let len = specializer::read_to_end_fast(&mut cursor, &mut buf).unwrap();
+///
+/// assert_eq!(len, 11);
+/// assert_eq!(buf, b"> Hello world");
+/// assert_eq!(specializer::remaining_bytes(&cursor), Some(&b""[..]));
+/// ```
+pub fn read_to_end_fast<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<usize>
+where
+    R: Read + 'static,
+{
+    let append = |bytes: &[u8]| {
+        buf.extend_from_slice(bytes);
+        bytes.len()
+    };
+
+    if let Ok(len) = dispatch_read(reader, append) {
+        return Ok(len);
+    }
+
+    reader.read_to_end(buf)
+}
+
+/// Copy the entire contents of `reader` into `writer`, taking fast paths for
+/// in-memory readers and writers.
+///
+/// Readers recognized by [`remaining_bytes()`] are written with a single
+/// [`Write::write_all()`] (or appended directly for `Vec<u8>` writers)
+/// instead of through an intermediate buffer; otherwise this is
+/// [`io::copy()`].  Reads and writes failing with
+/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted) are retried.
+///
+/// ```rust
+/// let mut reader: &'static [u8] = b"Hello world";
+/// let mut writer = Vec::new();
+///
+/// assert_eq!(specializer::copy_fast(&mut reader, &mut writer).unwrap(), 11);
+/// assert_eq!(writer, b"Hello world");
+/// assert!(reader.is_empty());
+/// ```
+pub fn copy_fast<R, W>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+where
+    R: Read + 'static,
+    W: Write + 'static,
+{
+    let write = |bytes: &[u8]| -> io::Result<u64> {
+        let append = |vec: &mut Vec<u8>| vec.extend_from_slice(bytes);
+
+        if dispatch_write(writer, append).is_err() {
+            writer.write_all(bytes)?;
+        }
+
+        Ok(bytes.len() as u64)
+    };
+
+    match dispatch_read(reader, write) {
+        Ok(len) => len,
+        Err(_) => io::copy(reader, writer),
+    }
+}
+
+fn cursor_remaining<T>(cursor: &Cursor<T>) -> &[u8]
+where
+    T: AsRef<[u8]>,
+{
+    let bytes = cursor.get_ref().as_ref();
+    let position = cursor.position().min(bytes.len() as u64) as usize;
+
+    &bytes[position..]
+}
+
+/// Mark `amount` bytes of a reader recognized by [`remaining_bytes()`] as
+/// read.
+pub(crate) fn consume<R>(reader: &mut R, amount: usize)
+where
+    R: 'static,
+{
+    if let Some(slice) = crate::cast_identity_mut::<R, &[u8]>(reader) {
+        slice.consume(amount);
+    } else if let Some(cursor) =
+        crate::cast_identity_mut::<R, Cursor<Vec<u8>>>(reader)
+    {
+        cursor.consume(amount);
+    } else if let Some(cursor) =
+        crate::cast_identity_mut::<R, Cursor<Box<[u8]>>>(reader)
+    {
+        cursor.consume(amount);
+    } else if let Some(cursor) =
+        crate::cast_identity_mut::<R, Cursor<&[u8]>>(reader)
+    {
+        cursor.consume(amount);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::SpecializerMut;
+
+/// Extension trait for mapping iterator items with specialized arms
+pub trait SpecializeItems: Iterator + Sized {
+    /// Map the items of this iterator with `f`, unless an arm added to the
+    /// returned [`IterSpecializer`] specializes on the item type.
+    ///
+    /// The arms are built once, rather than for each item.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use specializer::SpecializeItems;
+    ///
+    /// fn lengths<T>(items: Vec<T>) -> Vec<usize>
+    /// where
+    ///     T: 'static + ToString,
+    /// {
+    ///     items
+    ///         .into_iter()
+    ///         .specialize_items(|item| item.to_string().len())
+    ///         .specialize_param(|string: Arc<str>| string.len())
+    ///         .collect()
+    /// }
+    ///
+    /// assert_eq!(lengths(vec![Arc::<str>::from("a"), "bc".into()]), [1, 2]);
+    /// assert_eq!(lengths(vec![1, 22, 333]), [1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    fn specialize_items<U, F>(self, f: F) -> IterSpecializer<Self, U, F>
+    where
+        F: FnMut(Self::Item) -> U,
+        Self::Item: 'static,
+        U: 'static,
+    {
+        IterSpecializer::new(self, f)
+    }
+}
+
+impl<I> SpecializeItems for I where I: Iterator {}
+
+/// Specialized iterator item mapper
+///
+/// An [`Iterator`] that maps each item of the wrapped iterator with a
+/// [`SpecializerMut`] built from arms specialized on the item type, with a
+/// fallback mapping for every other type.  Usually created with
+/// [`SpecializeItems::specialize_items()`].
+#[derive(Debug)]
+pub struct IterSpecializer<I, U, F>(I, SpecializerMut<I::Item, U, F>)
+where
+    I: Iterator;
+
+impl<I, U, F> IterSpecializer<I, U, F>
+where
+    F: FnMut(I::Item) -> U,
+    I: Iterator,
+    I::Item: 'static,
+    U: 'static,
+{
+    /// Create a new iterator specializer with a fallback mapping.
+    #[inline(always)]
+    pub const fn new(iter: I, f: F) -> Self {
+        Self(iter, SpecializerMut::new(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize()`].
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnMut(P) -> R,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping both.
+    ///
+    /// See [`SpecializerMut::specialize_map()`].
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnMut(P) -> P,
+        f: impl FnMut(I::Item) -> U,
+        r: impl FnMut(R) -> R,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize_map(p, f, r))
+    }
+
+    /// Specialize on the item type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize_param()`].
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnMut(P) -> U,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        P: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize_param(f))
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize_return()`].
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnMut(I::Item) -> R,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        R: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize_return(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping the item.
+    ///
+    /// See [`SpecializerMut::specialize_map_param()`].
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnMut(P) -> P,
+        f: impl FnMut(I::Item) -> U,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        P: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize_map_param(p, f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping the return value.
+    ///
+    /// See [`SpecializerMut::specialize_map_return()`].
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnMut(I::Item) -> U,
+        r: impl FnMut(R) -> R,
+    ) -> IterSpecializer<I, U, impl FnMut(I::Item) -> U>
+    where
+        R: 'static,
+    {
+        IterSpecializer(self.0, self.1.specialize_map_return(f, r))
+    }
+
+    /// Return the wrapped iterator.
+    #[inline(always)]
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I, U, F> Iterator for IterSpecializer<I, U, F>
+where
+    F: FnMut(I::Item) -> U,
+    I: Iterator,
+    I::Item: 'static,
+    U: 'static,
+{
+    type Item = U;
+
+    #[inline]
+    fn next(&mut self) -> Option<U> {
+        self.0.next().map(|item| self.1.run_with(item))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I, U, F> DoubleEndedIterator for IterSpecializer<I, U, F>
+where
+    F: FnMut(I::Item) -> U,
+    I: DoubleEndedIterator,
+    I::Item: 'static,
+    U: 'static,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<U> {
+        self.0.next_back().map(|item| self.1.run_with(item))
+    }
+}
+
+impl<I, U, F> ExactSizeIterator for IterSpecializer<I, U, F>
+where
+    F: FnMut(I::Item) -> U,
+    I: ExactSizeIterator,
+    I::Item: 'static,
+    U: 'static,
+{
+}
@@ -12,7 +12,8 @@
 //!
 //! # Getting Started
 //!
-//! For the simplest example see [`Specializer::specialize_param()`].
+//! For the simplest example see [`Specializer::specialize_param()`].  The
+//! [`Specialize`] extension trait starts a specializer directly on a value.
 //!
 //! The other types may be required depending on your use case:
 //!
@@ -27,12 +28,217 @@
 //! | True  | Borrowed | Owned    | [`AsyncSpecializerBorrowedParam`]  |
 //! | True  | Borrowed | Borrowed | [`AsyncSpecializerBorrowed`]       |
 //!
+//! Other specializers cover more specific needs:
+//!  - [`SpecializerOption`] returns `None` (or hands back the parameter) when
+//!    no arm matches, for when there's no sensible fallback
+//!  - [`SpecializerChain`] and [`AsyncSpecializerChain`] are built once from
+//!    `Fn` arms before having the parameter (see [`Specializer::builder()`]),
+//!    and run repeatedly
+//!  - [`SpecializerMut`] and [`AsyncSpecializerMut`] are built from `FnMut`
+//!    arms that keep state between runs
+//!  - [`CheckedSpecializer`] can only be run once it has at least one arm
+//!  - [`TrySpecializer`] has fallible arms, so that `?` works inside of them
+//!  - [`SpecializerWithContext`] passes a `&mut` context to every arm
+//!  - [`Specializer2`] dispatches on two independent parameters
+//!  - [`SpecializerVisitor`] patches a `&mut T` in place if an arm specializes
+//!    on its type
+//!  - [`IterSpecializer`] (see [`SpecializeItems::specialize_items()`]) maps
+//!    the items of an iterator
+//!
+//! To check whether an arm would match without building a specializer, use
+//! [`would_specialize()`].
+//!
+//! ## Arm Order
+//!
+//! Each `specialize*()` call wraps the arms added before it, so arms added
+//! later are consulted first, and take priority when more than one arm
+//! matches (for example with [`Specializer::specialize_filter()`]).  To add
+//! arms that are consulted after the ones already added, build them into the
+//! fallback function with [`Specializer::builder()`]:
+//!
+//! ```rust
+//! use specializer::Specializer;
+//!
+//! fn describe<T: 'static>(ty: T) -> String {
+//!     let lower_priority = Specializer::builder(|_| "unknown".to_owned())
+//!         .specialize_param(|int: i32| format!("int {int}"))
+//!         .build();
+//!
+//!     Specializer::new(ty, lower_priority)
+//!         .specialize_filter(|int: &i32| *int < 0, |int: i32| -> String {
+//!             format!("negative {int}")
+//!         })
+//!         .run()
+//! }
+//!
+//! assert_eq!(describe(3), "int 3");
+//! assert_eq!(describe(-3), "negative -3");
+//! assert_eq!(describe(()), "unknown");
+//! ```
+//!
 //! ## Borrowing
 //!
 //! You can specialize on borrowed types using the `*SpecializerBorrowed*`
 //! specializers as long as the borrowed types implement
 //! [`CastIdentityBorrowed`], which is automatically implemented for `&T` and
-//! `&mut T`, `where T: 'static`.
+//! `&mut T`, `where T: 'static` (including unsized types like `str` and
+//! `[T]`), and with the `alloc` feature, for `Cow<'a, B>` and (forwarding to
+//! the boxed type) `Box<T>`.  Owned types are cast with [`cast_identity()`]
+//! instead; the two can't be merged into one trait (and one specializer type)
+//! without overlapping implementations, since `&'static T` is itself
+//! `'static`.
+//!
+//! Types containing borrows (like `MyStruct<'a>`) can opt in by implementing
+//! [`TypeFamily`] on a `'static` marker type, and are then specialized on
+//! wrapped in [`Branded`].  Owned values can be mixed into borrowed
+//! composites (like `(&'a mut A, Owned<B>)`) by wrapping them in [`Owned`],
+//! and values of a concrete type that never varies by wrapping them in
+//! [`Same`].
+//!
+//! ## Type Erasure
+//!
+//! Configured specializers of any of the above types can be stored and run
+//! uniformly behind `dyn Dispatch<U>` / `dyn AsyncDispatch<U>` (requires the
+//! `alloc` feature).
+//!
+//! ## Type-Driven Retrieval
+//!
+//! Error types and context objects can implement [`Provide`] to answer
+//! requests for references or values of arbitrary types (like a backtrace),
+//! made with [`request_ref()`] and [`request_value()`].
+//!
+//! ## Formatting Fast Paths
+//!
+//! [`write_display_fast()`] (and with the `alloc` feature, `display_str()`
+//! and `into_string_fast()`) skip the formatting machinery for string types,
+//! and with the `itoa` feature, format integers with `itoa`.
+//!
+//! ## IO Fast Paths
+//!
+//! With the `std` feature, `copy_fast()`, `read_to_end_fast()`, and
+//! `remaining_bytes()` skip intermediate buffers when generic readers and
+//! writers turn out to be in-memory byte buffers, and `dispatch_read()` and
+//! `dispatch_write()` run custom `ReadArms` and `WriteArms` for them.  The
+//! `futures-io` feature adds async counterparts for `AsyncRead` and
+//! `AsyncWrite`, and the `rkyv` feature borrows bytes and strings straight
+//! from archived values.
+//!
+//! ## Runtime Registration
+//!
+//! When the set of specialized types isn't known at compile time, arms can be
+//! registered at runtime with `DynSpecializer` (requires the `alloc`
+//! feature).  `DynRegistry` additionally keys arms on the requested return
+//! type, and `DynSpecializer2` dispatches on the runtime types of two
+//! parameters at once.  `AsyncDynSpecializer` registers async handlers.  With
+//! the `std` feature, a process-wide registry is available through
+//! `register_global()` and `dispatch_global()`; on `no_std` targets, the
+//! `critical-section` feature provides the same registry protected by a
+//! critical section instead.
+//!
+//! Since [`TypeId`](core::any::TypeId) isn't stable across compilations,
+//! registries shared with dynamically loaded plugins should use
+//! `StableRegistry`, which is keyed by a [`StableTypeId`] chosen by the author
+//! of each type (see [`stable_type!`]).
+//!
+//! With the `erased-serde` feature, `SerializeRegistry` serializes `&dyn Any`
+//! values with serializers registered per type at runtime.
+//!
+//! With the `bevy_reflect` feature, `ReflectSpecializer` registers arms for
+//! types in a `bevy_reflect` `TypeRegistry` by their type data, and
+//! `DynSpecializer::dispatch_reflect()` routes reflected values to typed arms.
+//!
+//! Values that are already type-erased as `Box<dyn Any>` can be routed to
+//! typed arms with `AnySpecializer` (requires the `alloc` feature), or without
+//! taking ownership from `&dyn Any` and `&mut dyn Any` with
+//! [`AnySpecializerRef`] and [`AnySpecializerMut`].
+//!
+//! For `no_std` targets without an allocator, [`DispatchTable`] (built with
+//! [`dispatch_table!`]) provides lookup from a fixed set of non-capturing
+//! handlers for [`StableType`]s, sorted at compile time.  For large closed
+//! sets, [`PerfectDispatchTable`] (built with [`perfect_dispatch_table!`])
+//! computes a perfect hash at compile time for O(1) lookup.
+//!
+//! ## Distributed Registration
+//!
+//! A [`StaticArm`] can be built in a `static`, so with the `linkme` feature
+//! dependencies can contribute arms to a distributed slice that the
+//! application dispatches on (or registers into a `DynSpecializer`).
+//!
+//! # Cargo Features
+//!
+//!  - `alloc`: Enable APIs that require an allocator
+//!  - `std`: Enable APIs that require the standard library (implies `alloc`)
+//!  - `bevy_reflect`: Enable `ReflectSpecializer` and
+//!    `DynSpecializer::dispatch_reflect()` for interop with
+//!    [`bevy_reflect`](https://docs.rs/bevy_reflect)'s `TypeRegistry` (implies
+//!    `alloc`)
+//!  - `bytemuck`: Enable layout-compatible (rather than identity) casts between
+//!    plain old data types with [`bytemuck`](https://docs.rs/bytemuck), with
+//!    `cast_layout()` and `Specializer::specialize_layout()`
+//!  - `castaway`: Re-export [`castaway`](https://docs.rs/castaway), and enable
+//!    the `Castaway` adapter for dispatching values that aren't `'static` to
+//!    arms taking lifetime-free types
+//!  - `downcast-rs`: Enable `from_downcast()` constructors on the `dyn Any`
+//!    specializers, for dispatching on the concrete type behind trait objects
+//!    of traits extending [`downcast-rs`](https://docs.rs/downcast-rs)'s
+//!    `Downcast` (implies `alloc`)
+//!  - `erased-serde`: Enable `SerializeRegistry`, for serializing `&dyn Any`
+//!    values with serializers registered at runtime through
+//!    [`erased-serde`](https://docs.rs/erased-serde) (implies `alloc` and
+//!    `serde`)
+//!  - `futures-channel`: Implement `CastIdentityBorrowed` for the `mpsc` and
+//!    `oneshot` channel endpoints from
+//!    [`futures-channel`](https://docs.rs/futures-channel), so they can be
+//!    specialized on their message type (implies `std`)
+//!  - `futures-core`: Enable `StreamSpecializer`, which maps the items of
+//!    `Stream`s from [`futures-core`](https://docs.rs/futures-core) with arms
+//!    specialized on the item type
+//!  - `futures-io`: Enable async IO fast paths for the `AsyncRead` and
+//!    `AsyncWrite` traits from [`futures-io`](https://docs.rs/futures-io)
+//!    (implies `std`)
+//!  - `hashbrown`: Use [`hashbrown`](https://docs.rs/hashbrown) instead of
+//!    `BTreeMap` to store large runtime registries (implies `alloc`)
+//!  - `itoa`: Format integers with [`itoa`](https://docs.rs/itoa) in the
+//!    formatting fast paths
+//!  - `linkme`: Re-export [`linkme`](https://docs.rs/linkme) for collecting
+//!    [`StaticArm`]s into distributed slices
+//!  - `num-traits`: Enable one-arm fast paths for all integer, signed integer,
+//!    or floating point types (like `Specializer::specialize_integer()`),
+//!    with bodies generic over [`num-traits`](https://docs.rs/num-traits)
+//!  - `rkyv`: Enable `archived_as()`, `archived_bytes()`, and `archived_str()`,
+//!    which pass [`rkyv`](https://docs.rs/rkyv) archived values through without
+//!    deserializing
+//!  - `serde`: Enable `serialize_fast()` and `deserialize_fast()`, which
+//!    take the [`serde`](https://docs.rs/serde) fast paths for byte and string
+//!    types, and the `SerializeArms` and `DeserializeArms` arm sets they're
+//!    built on
+//!  - `log`: Emit a `trace`-level [`log`](https://docs.rs/log) record for each
+//!    dispatch (registries, dispatch tables, and running a `Specializer` or
+//!    `SpecializerChain`), including the parameter type name and whether an arm
+//!    or the fallback ran
+//!  - `tracing`: Same as `log`, but with [`tracing`](https://docs.rs/tracing)
+//!    events
+//!  - `critical-section`: Enable the global registry without `std`, protected
+//!    by [`critical-section`](https://docs.rs/critical-section) (implies
+//!    `alloc`)
+//!  - `defmt`: Implement [`defmt::Format`](https://docs.rs/defmt) for the
+//!    crate's error and diagnostic types, and (on bare-metal targets) emit a
+//!    compact `defmt` trace message for each runtime dispatch
+//!  - `rayon`: Enable `DynSpecializer::par_dispatch()` and
+//!    `DynSpecializer::par_dispatch_each()` for dispatching in parallel with
+//!    [`rayon`](https://docs.rs/rayon) (implies `std`)
+//!  - `wasm-bindgen`: Enable `into_js_value_fast()` and
+//!    `from_js_value_fast()` for converting between generic values and
+//!    [`wasm-bindgen`](https://docs.rs/wasm-bindgen)'s `JsValue` (implies
+//!    `alloc`)
+//!  - `test-util`: Enable test support: `SpecializeProbe`,
+//!    `assert_specializes!`, and `MockCast` (implies `std`)
+//!  - `inline-always`: Force inlining of the structural casts so that dispatch
+//!    in unoptimized (debug) builds stays close to the cost of release builds,
+//!    at the expense of larger code in optimized builds
+//!  - `strict`: Check that `CastIdentityBorrowed` impls are consistent when an
+//!    arm is taken, panicking with the offending type pair instead of a bare
+//!    `unwrap()` failure
 
 #![doc(
     html_logo_url = "https://ardaku.github.io/mm/logo.svg",
@@ -68,29 +274,210 @@
     rustdoc::redundant_explicit_links
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod any_specializer;
+mod any_specializer_mut;
+mod any_specializer_ref;
 mod api;
+#[cfg(feature = "alloc")]
+mod arm_info;
+mod arms;
+#[cfg(feature = "alloc")]
+mod async_dyn_specializer;
 mod async_specializer;
 mod async_specializer_borrowed;
 mod async_specializer_borrowed_param;
 mod async_specializer_borrowed_return;
+mod async_specializer_chain;
+mod async_specializer_mut;
+mod branded;
+#[cfg(feature = "alloc")]
+mod cached_dispatch;
 mod cast_identity_borrowed;
+#[cfg(feature = "bytemuck")]
+mod cast_layout;
+#[cfg(feature = "castaway")]
+mod castaway_adapter;
+#[cfg(feature = "futures-channel")]
+mod channel;
+mod checked_specializer;
+#[cfg(feature = "alloc")]
+mod dispatch;
+mod dispatch_table;
+#[cfg(feature = "alloc")]
+mod dyn_registry;
+#[cfg(feature = "alloc")]
+mod dyn_specializer;
+#[cfg(feature = "alloc")]
+mod dyn_specializer2;
+mod fmt_fast;
+#[cfg(feature = "futures-io")]
+mod futures_io_fast;
+#[cfg(any(feature = "std", feature = "critical-section"))]
+mod global;
+#[cfg(feature = "std")]
+mod io_fast;
+mod iter_specializer;
+#[cfg(feature = "test-util")]
+mod mock_cast;
+#[cfg(feature = "num-traits")]
+mod numeric;
+mod owned;
+mod perfect_dispatch_table;
+mod probe;
+#[cfg(kani)]
+mod proofs;
+mod provide;
+#[cfg(feature = "bevy_reflect")]
+mod reflect_specializer;
+#[cfg(feature = "alloc")]
+mod register_error;
+#[cfg(feature = "alloc")]
+mod registration_scope;
+#[cfg(feature = "rkyv")]
+mod rkyv_fast;
+mod same;
+#[cfg(feature = "serde")]
+mod serde_fast;
+#[cfg(feature = "erased-serde")]
+mod serialize_registry;
+#[cfg(feature = "alloc")]
+mod small_map;
+mod specialize;
 mod specializer;
+mod specializer2;
 mod specializer_borrowed;
 mod specializer_borrowed_param;
 mod specializer_borrowed_return;
+mod specializer_chain;
+mod specializer_mut;
+mod specializer_option;
+mod specializer_visitor;
+mod specializer_with_context;
+#[cfg(feature = "alloc")]
+mod stable_registry;
+mod stable_type;
+mod static_arm;
+#[cfg(feature = "futures-core")]
+mod stream_specializer;
+mod trace;
+mod try_specializer;
+mod type_group;
+#[cfg(feature = "wasm-bindgen")]
+mod wasm_fast;
 
+#[cfg(feature = "castaway")]
+pub use castaway;
+#[cfg(feature = "linkme")]
+pub use linkme;
+
+#[cfg(feature = "bytemuck")]
+pub use self::cast_layout::{cast_layout, cast_layout_ref, cast_layout_slice};
+#[cfg(feature = "castaway")]
+pub use self::castaway_adapter::Castaway;
+#[doc(hidden)]
+pub use self::dispatch_table::__take_param;
+#[cfg(feature = "alloc")]
+pub use self::fmt_fast::{display_str, into_string_fast};
+#[cfg(feature = "futures-io")]
+pub use self::futures_io_fast::{
+    copy_fast_async, read_to_end_fast_async, write_vectored_fast_async,
+};
+#[cfg(any(feature = "std", feature = "critical-section"))]
+pub use self::global::{
+    dispatch_global, register_global, register_global_with_priority,
+};
+#[cfg(feature = "std")]
+pub use self::io_fast::{
+    ReadArms, WriteArms, copy_fast, dispatch_read, dispatch_write,
+    read_to_end_fast, remaining_bytes,
+};
+#[cfg(feature = "test-util")]
+pub use self::mock_cast::{
+    AlwaysMatch, MatchThenFail, MockCast, MockMode, NeverMatch,
+};
+#[cfg(feature = "num-traits")]
+pub use self::numeric::{
+    FloatArm, IntegerArm, SignedArm, dispatch_float, dispatch_integer,
+    dispatch_signed, is_float, is_integer, is_signed,
+};
+#[cfg(feature = "test-util")]
+#[doc(hidden)]
+pub use self::probe::__record;
+#[cfg(feature = "test-util")]
+pub use self::probe::SpecializeProbe;
+#[cfg(feature = "bevy_reflect")]
+pub use self::reflect_specializer::ReflectSpecializer;
+#[cfg(feature = "rkyv")]
+pub use self::rkyv_fast::{archived_as, archived_bytes, archived_str};
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub use self::serde_fast::{
+    DeserializeArms, deserialize_fast, dispatch_deserialize,
+};
+#[cfg(feature = "serde")]
+pub use self::serde_fast::{SerializeArms, dispatch_serialize, serialize_fast};
+#[cfg(feature = "erased-serde")]
+pub use self::serialize_registry::SerializeRegistry;
+#[cfg(feature = "futures-core")]
+pub use self::stream_specializer::StreamSpecializer;
+#[cfg(feature = "wasm-bindgen")]
+pub use self::wasm_fast::{from_js_value_fast, into_js_value_fast};
+#[cfg(feature = "alloc")]
+pub use self::{
+    any_specializer::AnySpecializer,
+    arm_info::ArmInfo,
+    async_dyn_specializer::AsyncDynSpecializer,
+    cached_dispatch::CachedDispatch,
+    dispatch::{AsyncDispatch, Dispatch},
+    dyn_registry::DynRegistry,
+    dyn_specializer::DynSpecializer,
+    dyn_specializer2::DynSpecializer2,
+    register_error::RegisterError,
+    registration_scope::RegistrationScope,
+    stable_registry::StableRegistry,
+};
 pub use self::{
+    any_specializer_mut::AnySpecializerMut,
+    any_specializer_ref::AnySpecializerRef,
     api::{
         cast_identity, cast_identity_borrowed, cast_identity_mut,
-        cast_identity_ref,
+        cast_identity_ref, would_specialize,
     },
+    arms::Arms,
     async_specializer::AsyncSpecializer,
     async_specializer_borrowed::AsyncSpecializerBorrowed,
     async_specializer_borrowed_param::AsyncSpecializerBorrowedParam,
     async_specializer_borrowed_return::AsyncSpecializerBorrowedReturn,
+    async_specializer_chain::AsyncSpecializerChain,
+    async_specializer_mut::AsyncSpecializerMut,
+    branded::{Branded, TypeFamily},
     cast_identity_borrowed::CastIdentityBorrowed,
+    checked_specializer::CheckedSpecializer,
+    dispatch_table::DispatchTable,
+    fmt_fast::write_display_fast,
+    iter_specializer::{IterSpecializer, SpecializeItems},
+    owned::Owned,
+    perfect_dispatch_table::PerfectDispatchTable,
+    provide::{Provide, Request, request_ref, request_value},
+    same::Same,
+    specialize::Specialize,
     specializer::Specializer,
     specializer_borrowed::SpecializerBorrowed,
     specializer_borrowed_param::SpecializerBorrowedParam,
     specializer_borrowed_return::SpecializerBorrowedReturn,
+    specializer_chain::SpecializerChain,
+    specializer_mut::SpecializerMut,
+    specializer_option::SpecializerOption,
+    specializer_visitor::SpecializerVisitor,
+    specializer_with_context::SpecializerWithContext,
+    specializer2::Specializer2,
+    stable_type::{StableType, StableTypeId},
+    static_arm::StaticArm,
+    try_specializer::TrySpecializer,
+    type_group::TypeGroup,
 };
@@ -0,0 +1,123 @@
+use core::marker::PhantomData;
+
+use crate::CastIdentityBorrowed;
+
+/// Forced behavior for a [`MockCast`]
+pub trait MockMode {
+    /// Value returned from [`CastIdentityBorrowed::is_same()`]
+    const IS_SAME: bool;
+    /// Whether [`CastIdentityBorrowed::cast_identity()`] succeeds
+    const CASTS: bool;
+}
+
+/// [`MockCast`] mode that matches every arm
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AlwaysMatch;
+
+/// [`MockCast`] mode that never matches an arm
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NeverMatch;
+
+/// [`MockCast`] mode that claims to match every arm, but fails the cast
+///
+/// This is a deliberately inconsistent [`CastIdentityBorrowed`]
+/// implementation, for testing how code handles one.  With the `strict`
+/// feature, a taken arm reports it by panicking with the type pair.
+///
+/// ```rust,should_panic
+/// use specializer::{MatchThenFail, MockCast, SpecializerBorrowedParam};
+///
+/// SpecializerBorrowedParam::new(MockCast::<MatchThenFail>::new(), |_| ())
+///     .specialize_param(|_: u8| ())
+///     .run();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchThenFail;
+
+impl MockMode for AlwaysMatch {
+    const CASTS: bool = true;
+    const IS_SAME: bool = true;
+}
+
+impl MockMode for NeverMatch {
+    const CASTS: bool = false;
+    const IS_SAME: bool = false;
+}
+
+impl MockMode for MatchThenFail {
+    const CASTS: bool = false;
+    const IS_SAME: bool = true;
+}
+
+/// Parameter with forced [`CastIdentityBorrowed`] behavior, for testing
+///
+/// `MockCast<M>` can be cast to any type implementing [`Default`] (including
+/// itself), and behaves according to its [`MockMode`]: with [`AlwaysMatch`]
+/// every arm matches and receives the default value of its parameter type,
+/// with [`NeverMatch`] no arm matches, and with [`MatchThenFail`] every arm
+/// matches but the cast fails.  This makes fallback logic and error handling
+/// deterministic to unit test.
+///
+/// Requires the `test-util` feature.
+///
+/// ```rust
+/// use specializer::{
+///     AlwaysMatch, MockCast, NeverMatch, SpecializerBorrowedParam,
+/// };
+///
+/// fn run<T>(param: T) -> &'static str
+/// where
+///     T: specializer::CastIdentityBorrowed<T>
+///         + specializer::CastIdentityBorrowed<u32>,
+/// {
+///     SpecializerBorrowedParam::new(param, |_| "fallback")
+///         .specialize_param(|_: u32| "arm")
+///         .run()
+/// }
+///
+/// assert_eq!(run(MockCast::<AlwaysMatch>::new()), "arm");
+/// assert_eq!(run(MockCast::<NeverMatch>::new()), "fallback");
+/// ```
+#[derive(Debug)]
+pub struct MockCast<M>(PhantomData<fn() -> M>);
+
+impl<M> MockCast<M>
+where
+    M: MockMode,
+{
+    /// Create a new mock parameter.
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M> Clone for MockCast<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for MockCast<M> {}
+
+impl<M> Default for MockCast<M>
+where
+    M: MockMode,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M, U> CastIdentityBorrowed<U> for MockCast<M>
+where
+    M: MockMode,
+    U: Default,
+{
+    fn cast_identity(self) -> Option<U> {
+        M::CASTS.then(U::default)
+    }
+
+    fn is_same() -> bool {
+        M::IS_SAME
+    }
+}
@@ -0,0 +1,190 @@
+use num_traits::{Float, PrimInt, Signed};
+
+/// Arm run for any primitive integer type
+///
+/// Since closures can't be generic, the arm is a trait with a generic method,
+/// so that one body covers every integer type.
+///
+/// ```rust
+/// use num_traits::PrimInt;
+/// use specializer::{IntegerArm, Specializer};
+///
+/// struct LeadingZeros;
+///
+/// impl IntegerArm<Option<u32>> for LeadingZeros {
+///     fn call<I: PrimInt>(self, int: I) -> Option<u32> {
+///         Some(int.leading_zeros())
+///     }
+/// }
+///
+/// fn leading_zeros<T: 'static>(ty: T) -> Option<u32> {
+///     Specializer::new(ty, |_| None)
+///         .specialize_integer(LeadingZeros)
+///         .run()
+/// }
+///
+/// assert_eq!(leading_zeros(1u8), Some(7));
+/// assert_eq!(leading_zeros(1i64), Some(63));
+/// assert_eq!(leading_zeros(1.0f32), None);
+/// ```
+pub trait IntegerArm<U> {
+    /// Run the arm.
+    fn call<I>(self, int: I) -> U
+    where
+        I: PrimInt + 'static;
+}
+
+/// Arm run for any primitive signed integer type
+///
+/// ```rust
+/// use num_traits::{PrimInt, Signed};
+/// use specializer::SignedArm;
+///
+/// struct IsNegative;
+///
+/// impl SignedArm<bool> for IsNegative {
+///     fn call<I: PrimInt + Signed>(self, int: I) -> bool {
+///         int.is_negative()
+///     }
+/// }
+///
+/// assert_eq!(specializer::dispatch_signed(-3i16, IsNegative), Ok(true));
+/// assert_eq!(specializer::dispatch_signed(3i128, IsNegative), Ok(false));
+/// assert_eq!(specializer::dispatch_signed(3u8, IsNegative), Err(3));
+/// ```
+pub trait SignedArm<U> {
+    /// Run the arm.
+    fn call<I>(self, int: I) -> U
+    where
+        I: PrimInt + Signed + 'static;
+}
+
+/// Arm run for any primitive floating point type
+///
+/// ```rust
+/// use num_traits::Float;
+/// use specializer::FloatArm;
+///
+/// struct Sqrt;
+///
+/// impl FloatArm<f64> for Sqrt {
+///     fn call<F: Float>(self, float: F) -> f64 {
+///         float.sqrt().to_f64().unwrap()
+///     }
+/// }
+///
+/// assert_eq!(specializer::dispatch_float(4.0f32, Sqrt), Ok(2.0));
+/// assert_eq!(specializer::dispatch_float(9.0f64, Sqrt), Ok(3.0));
+/// assert_eq!(specializer::dispatch_float(4u32, Sqrt), Err(4));
+/// ```
+pub trait FloatArm<U> {
+    /// Run the arm.
+    fn call<F>(self, float: F) -> U
+    where
+        F: Float + 'static;
+}
+
+macro_rules! integers {
+    ($m:ident!($($args:tt)*)) => {
+        $m!(
+            $($args)*;
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+        )
+    };
+}
+
+macro_rules! signed {
+    ($m:ident!($($args:tt)*)) => {
+        $m!($($args)*; i8, i16, i32, i64, i128, isize)
+    };
+}
+
+macro_rules! floats {
+    ($m:ident!($($args:tt)*)) => {
+        $m!($($args)*; f32, f64)
+    };
+}
+
+macro_rules! is_any {
+    ($t:ident; $($ty:ty),*) => {
+        false $(|| crate::api::type_eq::<$t, $ty>())*
+    };
+}
+
+macro_rules! dispatch {
+    ($t:ident, $value:ident, $arm:ident; $($ty:ty),*) => {{
+        $(
+            if crate::api::type_eq::<$t, $ty>() {
+                let value = crate::cast_identity::<$t, $ty>($value).unwrap();
+
+                return Ok($arm.call(value));
+            }
+        )*
+
+        Err($value)
+    }};
+}
+
+/// Return true if `T` is a primitive integer type.
+pub fn is_integer<T>() -> bool
+where
+    T: 'static,
+{
+    integers!(is_any!(T))
+}
+
+/// Return true if `T` is a primitive signed integer type.
+pub fn is_signed<T>() -> bool
+where
+    T: 'static,
+{
+    signed!(is_any!(T))
+}
+
+/// Return true if `T` is a primitive floating point type.
+///
+/// ```rust
+/// assert!(specializer::is_float::<f32>());
+/// assert!(!specializer::is_float::<i32>());
+/// assert!(specializer::is_integer::<u8>());
+/// assert!(!specializer::is_signed::<u8>());
+/// ```
+pub fn is_float<T>() -> bool
+where
+    T: 'static,
+{
+    floats!(is_any!(T))
+}
+
+/// Run `arm` if `value` is of a primitive integer type.
+///
+/// Returns `Err(value)` otherwise.
+#[inline]
+pub fn dispatch_integer<T, U>(value: T, arm: impl IntegerArm<U>) -> Result<U, T>
+where
+    T: 'static,
+{
+    integers!(dispatch!(T, value, arm))
+}
+
+/// Run `arm` if `value` is of a primitive signed integer type.
+///
+/// Returns `Err(value)` otherwise.
+#[inline]
+pub fn dispatch_signed<T, U>(value: T, arm: impl SignedArm<U>) -> Result<U, T>
+where
+    T: 'static,
+{
+    signed!(dispatch!(T, value, arm))
+}
+
+/// Run `arm` if `value` is of a primitive floating point type.
+///
+/// Returns `Err(value)` otherwise.
+#[inline]
+pub fn dispatch_float<T, U>(value: T, arm: impl FloatArm<U>) -> Result<U, T>
+where
+    T: 'static,
+{
+    floats!(dispatch!(T, value, arm))
+}
@@ -0,0 +1,50 @@
+use crate::CastIdentityBorrowed;
+
+/// Adapter for mixing owned values into borrowed composites
+///
+/// [`CastIdentityBorrowed`] is implemented from `Owned<T>` to `Owned<U>`,
+/// `where T: 'static, U: 'static`, so that owned values can be passed to the
+/// borrowed specializers alongside borrows, like in `(&'a mut A, Owned<B>)`.
+///
+/// ```rust
+/// use specializer::{Owned, SpecializerBorrowed};
+///
+/// fn push<'a, T, U>(list: &'a mut Vec<T>, item: U) -> Option<&'a T>
+/// where
+///     T: 'static,
+///     U: 'static,
+/// {
+///     type Params<'a> = (&'a mut Vec<u32>, Owned<u32>);
+///
+///     SpecializerBorrowed::new((list, Owned(item)), |_| None)
+///         .specialize(|(list, Owned(item)): Params<'a>| -> Option<&'a u32> {
+///             list.push(item);
+///             list.last()
+///         })
+///         .run()
+/// }
+///
+/// let mut ints = vec![1u32, 2];
+///
+/// assert_eq!(push(&mut ints, 3u32), Some(&3));
+/// assert_eq!(push(&mut ints, 4u8), None);
+/// assert_eq!(ints, [1, 2, 3]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Owned<T>(pub T);
+
+impl<T, U> CastIdentityBorrowed<Owned<U>> for Owned<T>
+where
+    T: 'static,
+    U: 'static,
+{
+    #[cfg_attr(feature = "inline-always", inline(always))]
+    fn cast_identity(self) -> Option<Owned<U>> {
+        Some(Owned(crate::cast_identity(self.0)?))
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        crate::api::type_eq::<U, T>()
+    }
+}
@@ -0,0 +1,256 @@
+use core::{any::Any, marker::PhantomData};
+
+use crate::{StableType, StableTypeId};
+
+/// Check that a `PhantomData` is of the parameter type, and the handler
+type Arm<U> = (fn(&dyn Any) -> bool, fn(&mut dyn Any) -> U);
+
+/// Build a [`PerfectDispatchTable`] from a closed list of
+/// `ParamType => handler` pairs.
+///
+/// Parameter types must implement [`StableType`], and handlers must not
+/// capture their environment (they are coerced to function pointers).  The
+/// table can be built in a `static`, in which case the perfect hash is
+/// computed at compile time.
+///
+/// ```rust
+/// use specializer::{PerfectDispatchTable, perfect_dispatch_table};
+///
+/// static TABLE: PerfectDispatchTable<String, 4> = perfect_dispatch_table![
+///     i32 => |int| (int * 2).to_string(),
+///     u8 => |int| (int * 3).to_string(),
+///     bool => |boolean| (!boolean).to_string(),
+///     char => |character| character.to_uppercase().collect(),
+/// ];
+///
+/// fn describe<T: specializer::StableType>(ty: T) -> String {
+///     TABLE.dispatch(ty).unwrap_or_else(|_| "unknown".to_owned())
+/// }
+///
+/// assert_eq!(describe(3i32), "6");
+/// assert_eq!(describe(3u8), "9");
+/// assert_eq!(describe(true), "false");
+/// assert_eq!(describe('a'), "A");
+/// assert_eq!(describe(()), "unknown");
+/// ```
+#[macro_export]
+macro_rules! perfect_dispatch_table {
+    ($($param:ty => $f:expr),* $(,)?) => {
+        $crate::PerfectDispatchTable::new([$((
+            <$param as $crate::StableType>::STABLE_TYPE_ID,
+            (
+                |ty: &dyn ::core::any::Any| {
+                    ty.is::<::core::marker::PhantomData<$param>>()
+                },
+                |any: &mut dyn ::core::any::Any| {
+                    let f: fn($param) -> _ = $f;
+
+                    f($crate::__take_param::<$param>(any))
+                },
+            ),
+        )),*])
+    };
+}
+
+/// Allocation-free dispatch table with O(1) lookup (Owned -> Owned)
+///
+/// Entries are keyed by [`StableTypeId`], which (unlike
+/// [`TypeId`](core::any::TypeId)) is available in const contexts, so a
+/// minimal perfect hash ("hash and displace") over the closed set of keys is
+/// computed by the const constructor.  Dispatch then hashes the key twice and
+/// checks a single slot, regardless of the number of entries.  Use the
+/// [`perfect_dispatch_table!`](crate::perfect_dispatch_table) macro to build
+/// one.
+#[derive(Debug)]
+pub struct PerfectDispatchTable<U, const N: usize> {
+    /// Displacement seed for each bucket
+    seeds: [u64; N],
+    /// Entries, placed at the slot of their key
+    entries: [(StableTypeId, Arm<U>); N],
+}
+
+impl<U, const N: usize> PerfectDispatchTable<U, N> {
+    /// Create a new dispatch table from a list of entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time, if evaluated in a const context) if more than
+    /// one entry has the same [`StableTypeId`].
+    pub const fn new(entries: [(StableTypeId, Arm<U>); N]) -> Self {
+        let mut i = 0;
+
+        while i < N {
+            let mut j = i + 1;
+
+            while j < N {
+                assert!(
+                    entries[i].0.as_u128() != entries[j].0.as_u128(),
+                    "duplicate perfect dispatch table entry",
+                );
+                j += 1;
+            }
+            i += 1;
+        }
+
+        let mut buckets = [0; N];
+        let mut sizes = [0; N];
+        let mut largest = 0;
+        let mut i = 0;
+
+        while i < N {
+            buckets[i] = bucket(entries[i].0, N);
+            sizes[buckets[i]] += 1;
+            if sizes[buckets[i]] > largest {
+                largest = sizes[buckets[i]];
+            }
+            i += 1;
+        }
+
+        // Place the largest buckets first, while most slots are still free
+        let mut seeds = [0; N];
+        let mut taken = [false; N];
+        let mut placed = entries;
+        let mut size = largest;
+
+        while size > 0 {
+            let mut b = 0;
+
+            while b < N {
+                if sizes[b] == size {
+                    let seed = displace(&entries, &buckets, &mut taken, b);
+                    let mut i = 0;
+
+                    seeds[b] = seed;
+                    while i < N {
+                        if buckets[i] == b {
+                            placed[slot(entries[i].0, seed, N)] = entries[i];
+                        }
+                        i += 1;
+                    }
+                }
+                b += 1;
+            }
+            size -= 1;
+        }
+
+        Self {
+            seeds,
+            entries: placed,
+        }
+    }
+
+    /// Return true if an entry exists for parameter type `P`.
+    pub fn contains<P>(&self) -> bool
+    where
+        P: StableType,
+    {
+        self.find(P::STABLE_TYPE_ID)
+            .is_some_and(|(accepts, _)| accepts(&PhantomData::<P>))
+    }
+
+    /// Run the entry for the type of `param`.
+    ///
+    /// Returns `Err(param)` if there is no entry for `T`, including when the
+    /// entry for the [`StableTypeId`] of `T` is for another type.
+    ///
+    /// ```rust
+    /// use specializer::{PerfectDispatchTable, perfect_dispatch_table};
+    ///
+    /// struct Ping(u32);
+    /// struct Pong(u32);
+    ///
+    /// // Mistakenly published with the same identifier
+    /// specializer::stable_type! {
+    ///     Ping => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f,
+    ///     Pong => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f,
+    /// }
+    ///
+    /// static TABLE: PerfectDispatchTable<u32, 1> =
+    ///     perfect_dispatch_table![Ping => |ping| ping.0];
+    ///
+    /// assert_eq!(TABLE.dispatch(Ping(7)).ok(), Some(7));
+    /// assert_eq!(TABLE.dispatch(Pong(8)).map_err(|pong| pong.0), Err(8));
+    /// ```
+    #[inline]
+    pub fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: StableType,
+    {
+        let arm = self
+            .find(T::STABLE_TYPE_ID)
+            .filter(|(accepts, _)| accepts(&PhantomData::<T>));
+        let Some((_, run)) = crate::trace::found::<T, _>(arm) else {
+            return Err(param);
+        };
+
+        Ok(run(&mut Some(param)))
+    }
+
+    fn find(&self, id: StableTypeId) -> Option<Arm<U>> {
+        if N == 0 {
+            return None;
+        }
+
+        let seed = self.seeds[bucket(id, N)];
+        let (key, arm) = self.entries[slot(id, seed, N)];
+
+        (key == id).then_some(arm)
+    }
+}
+
+/// Find a seed that moves every key in bucket `b` to a distinct free slot,
+/// and mark those slots as taken.
+const fn displace<U, const N: usize>(
+    entries: &[(StableTypeId, Arm<U>); N],
+    buckets: &[usize; N],
+    taken: &mut [bool; N],
+    b: usize,
+) -> u64 {
+    let mut seed = 1;
+
+    loop {
+        let mut trial = *taken;
+        let mut i = 0;
+        let mut fits = true;
+
+        while fits && i < N {
+            if buckets[i] == b {
+                let slot = slot(entries[i].0, seed, N);
+
+                fits = !trial[slot];
+                trial[slot] = true;
+            }
+            i += 1;
+        }
+
+        if fits {
+            *taken = trial;
+            return seed;
+        }
+
+        assert!(seed < 1 << 20, "failed to build perfect hash");
+        seed += 1;
+    }
+}
+
+const fn bucket(id: StableTypeId, n: usize) -> usize {
+    (hash(id, 0) % n as u64) as usize
+}
+
+const fn slot(id: StableTypeId, seed: u64, n: usize) -> usize {
+    (hash(id, seed) % n as u64) as usize
+}
+
+/// Seeded 64-bit hash of a stable type identifier (splitmix64 finalizer)
+const fn hash(id: StableTypeId, seed: u64) -> u64 {
+    let id = id.as_u128();
+    let mut x = (id as u64)
+        ^ ((id >> 64) as u64).rotate_left(32)
+        ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
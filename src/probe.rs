@@ -0,0 +1,217 @@
+#[cfg(feature = "test-util")]
+use std::{cell::RefCell, marker::PhantomData, vec::Vec};
+
+/// Parameter type name of a consulted arm, and whether it matched
+#[cfg(feature = "test-util")]
+type Event = (&'static str, bool);
+
+#[cfg(feature = "test-util")]
+std::thread_local! {
+    /// Arms consulted while a probe is active (`None` when none is)
+    static EVENTS: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+/// Record that the arm for parameter type `P` was taken.
+///
+/// Does nothing without the `test-util` feature.
+#[inline(always)]
+pub(crate) fn taken<P>()
+where
+    P: ?Sized,
+{
+    dispatched(core::any::type_name::<P>(), true);
+}
+
+/// Record that the arm for parameter type `P` was consulted, but didn't
+/// match.
+///
+/// Does nothing without the `test-util` feature.
+#[inline(always)]
+pub(crate) fn missed<P>()
+where
+    P: ?Sized,
+{
+    dispatched(core::any::type_name::<P>(), false);
+}
+
+/// Record that the arm for the parameter type named `param` was consulted,
+/// and whether it `matched`.
+///
+/// Does nothing without the `test-util` feature.
+#[inline(always)]
+pub(crate) fn dispatched(param: &'static str, matched: bool) {
+    #[cfg(feature = "test-util")]
+    EVENTS.with_borrow_mut(|events| {
+        if let Some(events) = events {
+            events.push((param, matched));
+        }
+    });
+
+    #[cfg(not(feature = "test-util"))]
+    let _ = (param, matched);
+}
+
+/// Guard recording which arms are consulted, and which match, on the current
+/// thread while it's alive
+///
+/// Arms are identified by the [`type_name()`](core::any::type_name) of their
+/// parameter type, and are recorded in the order they're checked.  Since each
+/// call to `specialize()` wraps the chain built so far, that's the most
+/// recently added arm first.  Dispatch through the runtime registries is
+/// recorded as a single arm for the type of the parameter.
+///
+/// Creating a probe while another is alive pauses the outer probe until the
+/// inner one is dropped.
+///
+/// Requires the `test-util` feature.
+///
+/// ```rust
+/// use specializer::{SpecializeProbe, Specializer};
+///
+/// fn describe<T: 'static>(ty: T) -> String {
+///     Specializer::new(ty, |_| "unknown".to_owned())
+///         .specialize_param(|int: u32| int.to_string())
+///         .specialize_param(|int: i32| int.to_string())
+///         .run()
+/// }
+///
+/// let probe = SpecializeProbe::new();
+///
+/// describe(3u32);
+///
+/// assert_eq!(probe.consulted(), ["i32", "u32"]);
+/// assert_eq!(probe.matched(), ["u32"]);
+///
+/// probe.clear();
+/// describe(());
+///
+/// assert_eq!(probe.consulted(), ["i32", "u32"]);
+/// assert!(probe.matched().is_empty());
+/// ```
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub struct SpecializeProbe {
+    /// Events recorded by the probe this one paused
+    outer: Option<Vec<Event>>,
+    /// Recording is per-thread, so the probe must stay on its thread
+    _thread: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "test-util")]
+impl SpecializeProbe {
+    /// Start recording on the current thread.
+    pub fn new() -> Self {
+        let outer = EVENTS.replace(Some(Vec::new()));
+
+        Self {
+            outer,
+            _thread: PhantomData,
+        }
+    }
+
+    /// Return the parameter type names of the consulted arms, in the order
+    /// they were checked.
+    pub fn consulted(&self) -> Vec<&'static str> {
+        self.events(|_| true)
+    }
+
+    /// Return the parameter type names of the arms that matched, in the order
+    /// they were taken.
+    pub fn matched(&self) -> Vec<&'static str> {
+        self.events(|matched| matched)
+    }
+
+    /// Forget the arms recorded so far.
+    pub fn clear(&self) {
+        EVENTS.with_borrow_mut(|events| {
+            if let Some(events) = events {
+                events.clear();
+            }
+        });
+    }
+
+    fn events(&self, f: impl Fn(bool) -> bool) -> Vec<&'static str> {
+        EVENTS.with_borrow(|events| {
+            events
+                .iter()
+                .flatten()
+                .filter_map(|&(name, matched)| f(matched).then_some(name))
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for SpecializeProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Drop for SpecializeProbe {
+    fn drop(&mut self) {
+        EVENTS.set(self.outer.take());
+    }
+}
+
+/// Run `f`, returning its output and the parameter type name of the first
+/// specialized arm taken.
+#[cfg(feature = "test-util")]
+#[doc(hidden)]
+pub fn __record<O>(f: impl FnOnce() -> O) -> (O, Option<&'static str>) {
+    let probe = SpecializeProbe::new();
+    let output = f();
+    let arm = probe.matched().first().copied();
+
+    (output, arm)
+}
+
+/// Assert that an expression takes a specialized arm (or the fallback).
+///
+/// The arm is identified by the [`type_name()`](core::any::type_name) of its
+/// parameter type, and only the first arm taken while evaluating the
+/// expression (on the current thread) is checked, so arms of specializers
+/// nested inside it don't count.  This locks in fast paths with tests, so
+/// that a change to a bound or a type doesn't silently send calls to the
+/// fallback.
+///
+/// Requires the `test-util` feature.
+///
+/// ```rust
+/// use specializer::{Specializer, assert_specializes};
+///
+/// fn describe<T: 'static>(ty: T) -> String {
+///     Specializer::new(ty, |_| "unknown".to_owned())
+///         .specialize_param(|int: u32| int.to_string())
+///         .run()
+/// }
+///
+/// assert_specializes!(describe(3u32), arm = "u32");
+/// assert_specializes!(describe(3i32), fallback);
+/// ```
+#[cfg(feature = "test-util")]
+#[macro_export]
+macro_rules! assert_specializes {
+    ($call:expr, arm = $arm:expr $(,)?) => {{
+        let (_, arm) = $crate::__record(|| $call);
+
+        assert_eq!(
+            arm,
+            Some($arm),
+            "expected `{}` to take the `{}` arm",
+            stringify!($call),
+            $arm,
+        );
+    }};
+    ($call:expr, fallback $(,)?) => {{
+        let (_, arm) = $crate::__record(|| $call);
+
+        assert_eq!(
+            arm,
+            None,
+            "expected `{}` to take the fallback",
+            stringify!($call),
+        );
+    }};
+}
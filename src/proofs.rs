@@ -0,0 +1,128 @@
+//! [Kani](https://model-checking.github.io/kani/) proof harnesses for the
+//! cast invariants
+//!
+//! Run with `cargo kani`.  Each harness checks that a cast never produces a
+//! value when the types differ, and that when they match the value is moved
+//! (or reborrowed) rather than lost or duplicated.
+
+use core::{
+    pin::Pin,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    cast_identity, cast_identity_borrowed, cast_identity_mut, cast_identity_ref,
+};
+
+/// Number of times a [`Tracked`] has been dropped
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// Value that counts its drops in [`DROPS`]
+struct Tracked(u32);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn drops() -> usize {
+    DROPS.load(Ordering::Relaxed)
+}
+
+#[kani::proof]
+fn owned_same_type_moves() {
+    let int = kani::any();
+    let cast = cast_identity::<Tracked, Tracked>(Tracked(int));
+
+    assert_eq!(drops(), 0);
+
+    let tracked = cast.unwrap();
+
+    assert_eq!(tracked.0, int);
+    drop(tracked);
+    assert_eq!(drops(), 1);
+}
+
+#[kani::proof]
+fn owned_different_type_drops_once() {
+    let cast = cast_identity::<Tracked, u32>(Tracked(kani::any()));
+
+    assert!(cast.is_none());
+    assert_eq!(drops(), 1);
+}
+
+#[kani::proof]
+fn ref_same_type_reborrows() {
+    let int: u32 = kani::any();
+    let cast = cast_identity_ref::<u32, u32>(&int).unwrap();
+
+    assert!(ptr::eq(cast, &int));
+    assert!(cast_identity_ref::<u32, i32>(&int).is_none());
+}
+
+#[kani::proof]
+fn mut_same_type_writes_through() {
+    let mut int: u32 = kani::any();
+    let new = kani::any();
+
+    assert!(cast_identity_mut::<u32, i32>(&mut int).is_none());
+    *cast_identity_mut::<u32, u32>(&mut int).unwrap() = new;
+    assert_eq!(int, new);
+}
+
+#[kani::proof]
+fn borrowed_same_shape_reborrows() {
+    let int: u32 = kani::any();
+    let mut long: u64 = kani::any();
+    let new = kani::any();
+    let (int_ref, long_mut) = cast_identity_borrowed::<
+        (&u32, Option<&mut u64>),
+        (&u32, Option<&mut u64>),
+    >((&int, Some(&mut long)))
+    .unwrap();
+
+    assert!(ptr::eq(int_ref, &int));
+    *long_mut.unwrap() = new;
+    assert_eq!(long, new);
+}
+
+#[kani::proof]
+fn borrowed_different_shape_fails() {
+    let mut int: u32 = kani::any();
+    let mut long: u64 = kani::any();
+
+    assert!(
+        cast_identity_borrowed::<
+            (&u32, Option<&mut u64>),
+            (&u32, Option<&mut i64>),
+        >((&int, Some(&mut long)))
+        .is_none(),
+    );
+    assert!(
+        cast_identity_borrowed::<Result<&u32, &u64>, Result<&u32, &i64>>(
+            if kani::any() { Ok(&int) } else { Err(&long) },
+        )
+        .is_none(),
+    );
+    assert!(
+        cast_identity_borrowed::<Pin<&mut u32>, Pin<&mut i32>>(Pin::new(
+            &mut int
+        ))
+        .is_none(),
+    );
+}
+
+#[kani::proof]
+fn borrowed_pinned_writes_through() {
+    let mut int: u32 = kani::any();
+    let new = kani::any();
+    let mut pinned = cast_identity_borrowed::<Pin<&mut u32>, Pin<&mut u32>>(
+        Pin::new(&mut int),
+    )
+    .unwrap();
+
+    *pinned = new;
+    assert_eq!(int, new);
+}
@@ -0,0 +1,199 @@
+use core::{
+    any::{self, Any, TypeId},
+    fmt,
+};
+
+/// Type-driven retrieval of data from a provider
+///
+/// A stable counterpart of the unstable `core::error::Request` API: error
+/// types and context objects implement `Provide` to answer queries like "give
+/// me a `&Backtrace` if you have one", which callers make with
+/// [`request_ref()`] and [`request_value()`].
+///
+/// ```rust
+/// use specializer::{Provide, Request};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Backtrace(&'static str);
+///
+/// struct MyError {
+///     backtrace: Backtrace,
+///     code: u16,
+/// }
+///
+/// impl Provide for MyError {
+///     fn provide<'a>(&'a self, request: &mut Request<'a, '_>) {
+///         request
+///             .provide_ref(&self.backtrace)
+///             .provide_value(self.code)
+///             .provide_value_with(|| format!("error {}", self.code));
+///     }
+/// }
+///
+/// let error = MyError {
+///     backtrace: Backtrace("main.rs:1"),
+///     code: 404,
+/// };
+///
+/// assert_eq!(
+///     specializer::request_ref::<Backtrace, _>(&error),
+///     Some(&Backtrace("main.rs:1")),
+/// );
+/// assert_eq!(specializer::request_value::<u16, _>(&error), Some(404));
+/// assert_eq!(
+///     specializer::request_value::<String, _>(&error).as_deref(),
+///     Some("error 404"),
+/// );
+/// assert_eq!(specializer::request_ref::<u16, _>(&error), None);
+/// assert_eq!(specializer::request_value::<u32, _>(&error), None);
+/// ```
+pub trait Provide {
+    /// Provide data for `request`.
+    ///
+    /// Only the first provided item matching the requested type is kept.
+    fn provide<'a>(&'a self, request: &mut Request<'a, '_>);
+}
+
+/// Type-erased request for a reference or value of a specific type
+///
+/// Passed to [`Provide::provide()`].
+pub struct Request<'a, 'b>(&'b mut dyn Slot<'a>);
+
+impl<'a> Request<'a, '_> {
+    /// Provide a reference, if the request is for a `&U`.
+    pub fn provide_ref<U>(&mut self, value: &'a U) -> &mut Self
+    where
+        U: 'static,
+    {
+        if self.0.wants_ref(TypeId::of::<U>()) {
+            self.0.put_ref(value);
+        }
+
+        self
+    }
+
+    /// Provide a value, if the request is for a `U`.
+    pub fn provide_value<U>(&mut self, value: U) -> &mut Self
+    where
+        U: 'static,
+    {
+        self.provide_value_with(|| value)
+    }
+
+    /// Provide a value computed by `f`, if the request is for a `U`.
+    ///
+    /// `f` is only called if the request would be fulfilled by its output.
+    pub fn provide_value_with<U>(&mut self, f: impl FnOnce() -> U) -> &mut Self
+    where
+        U: 'static,
+    {
+        if self.0.wants_value(TypeId::of::<U>()) {
+            self.0.put_value(&mut Some(f()));
+        }
+
+        self
+    }
+
+    /// Return true if the request has already been fulfilled.
+    pub fn is_fulfilled(&self) -> bool {
+        self.0.is_fulfilled()
+    }
+}
+
+impl fmt::Debug for Request<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("type_name", &self.0.type_name())
+            .field("fulfilled", &self.0.is_fulfilled())
+            .finish()
+    }
+}
+
+/// Request a reference to a `T` from `provider`.
+pub fn request_ref<T, P>(provider: &P) -> Option<&T>
+where
+    T: 'static,
+    P: Provide + ?Sized,
+{
+    let mut slot = RefSlot::<T>(None);
+
+    provider.provide(&mut Request(&mut slot));
+    slot.0
+}
+
+/// Request a `T` by value from `provider`.
+pub fn request_value<T, P>(provider: &P) -> Option<T>
+where
+    T: 'static,
+    P: Provide + ?Sized,
+{
+    let mut slot = ValueSlot::<T>(None);
+
+    provider.provide(&mut Request(&mut slot));
+    slot.0
+}
+
+/// Storage for the answer to a [`Request`]
+trait Slot<'a> {
+    fn type_name(&self) -> &'static str;
+
+    fn is_fulfilled(&self) -> bool;
+
+    fn wants_ref(&self, _type_id: TypeId) -> bool {
+        false
+    }
+
+    fn wants_value(&self, _type_id: TypeId) -> bool {
+        false
+    }
+
+    fn put_ref(&mut self, _value: &'a dyn Any) {}
+
+    fn put_value(&mut self, _value: &mut dyn Any) {}
+}
+
+struct RefSlot<'a, T>(Option<&'a T>);
+
+impl<'a, T> Slot<'a> for RefSlot<'a, T>
+where
+    T: 'static,
+{
+    fn type_name(&self) -> &'static str {
+        any::type_name::<&T>()
+    }
+
+    fn is_fulfilled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn wants_ref(&self, type_id: TypeId) -> bool {
+        self.0.is_none() && type_id == TypeId::of::<T>()
+    }
+
+    fn put_ref(&mut self, value: &'a dyn Any) {
+        self.0 = value.downcast_ref();
+    }
+}
+
+struct ValueSlot<T>(Option<T>);
+
+impl<T> Slot<'_> for ValueSlot<T>
+where
+    T: 'static,
+{
+    fn type_name(&self) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    fn is_fulfilled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn wants_value(&self, type_id: TypeId) -> bool {
+        self.0.is_none() && type_id == TypeId::of::<T>()
+    }
+
+    fn put_value(&mut self, value: &mut dyn Any) {
+        self.0 = Some(crate::__take_param(value));
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::boxed::Box;
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+use bevy_reflect::{Reflect, TypeData, TypeRegistration, TypeRegistry};
+
+use crate::small_map::SmallMap;
+
+type Arm<U> = Box<dyn Fn(Box<dyn Reflect>) -> U + Send + Sync>;
+
+/// Runtime registry of specialized behavior for reflected values
+///
+/// Works like [`DynSpecializer`](crate::DynSpecializer), except that
+/// parameters are passed as `Box<dyn Reflect>` from
+/// [`bevy_reflect`](https://docs.rs/bevy_reflect), so arms can be registered
+/// for every type in a [`TypeRegistry`] carrying some type data, without
+/// naming the types.
+///
+/// ```rust
+/// use bevy_reflect::{Reflect, TypeRegistry, std_traits::ReflectDefault};
+/// use specializer::ReflectSpecializer;
+///
+/// #[derive(Reflect, Default)]
+/// #[reflect(Default)]
+/// struct Health(u32);
+///
+/// let mut registry = TypeRegistry::empty();
+///
+/// registry.register::<Health>();
+///
+/// let mut spec = ReflectSpecializer::new();
+///
+/// spec.register_type_data(&registry, |_: &ReflectDefault, value| {
+///     format!("{} (has default)", value.reflect_short_type_path())
+/// })
+/// .register(|int: i32| (int * 2).to_string());
+///
+/// assert_eq!(
+///     spec.dispatch(Box::new(Health(5))).ok().as_deref(),
+///     Some("Health (has default)"),
+/// );
+/// assert_eq!(spec.dispatch(Box::new(3i32)).ok().as_deref(), Some("6"));
+/// assert!(spec.dispatch(Box::new(3u8)).is_err());
+/// ```
+pub struct ReflectSpecializer<U> {
+    arms: SmallMap<TypeId, Arm<U>>,
+}
+
+impl<U> ReflectSpecializer<U> {
+    /// Create a new specializer without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+        }
+    }
+
+    /// Register an arm for parameter type `P`.
+    ///
+    /// Registering a second arm for the same parameter type replaces the
+    /// first.
+    pub fn register<P>(
+        &mut self,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: Reflect,
+    {
+        let arm = move |value: Box<dyn Reflect>| -> U {
+            f(*value.downcast::<P>().ok().unwrap())
+        };
+
+        self.arms.insert(TypeId::of::<P>(), Box::new(arm));
+        self
+    }
+
+    /// Register an arm for each type in `registry` with type data `D`.
+    ///
+    /// The arm is passed the type's data along with the reflected parameter.
+    /// Types registered with `registry` later aren't picked up.
+    pub fn register_type_data<D>(
+        &mut self,
+        registry: &TypeRegistry,
+        f: impl Fn(&D, Box<dyn Reflect>) -> U + Clone + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        D: TypeData + Clone,
+    {
+        for (registration, data) in registry.iter_with_data::<D>() {
+            let data = data.clone();
+            let f = f.clone();
+            let arm = move |value: Box<dyn Reflect>| -> U { f(&data, value) };
+
+            self.arms.insert(registration.type_id(), Box::new(arm));
+        }
+
+        self
+    }
+
+    /// Return true if an arm is registered for the type with `type_id`.
+    pub fn contains(&self, type_id: TypeId) -> bool {
+        self.arms.contains_key(&type_id)
+    }
+
+    /// Return the number of parameter types with registered arms.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Iterate over the registrations in `registry` with a registered arm.
+    pub fn registrations<'a>(
+        &'a self,
+        registry: &'a TypeRegistry,
+    ) -> impl Iterator<Item = &'a TypeRegistration> + 'a {
+        registry.iter().filter(|registration| {
+            self.contains(TypeRegistration::type_id(registration))
+        })
+    }
+
+    /// Run the arm registered for the reflected type of `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for the type.
+    #[inline]
+    pub fn dispatch(
+        &self,
+        param: Box<dyn Reflect>,
+    ) -> Result<U, Box<dyn Reflect>> {
+        let type_id = Any::type_id(param.as_any());
+        let name = param
+            .get_represented_type_info()
+            .map_or(crate::trace::UNKNOWN, |info| info.type_path());
+        let arm = crate::trace::found_named(name, self.arms.get(&type_id));
+        let Some(arm) = arm else {
+            return Err(param);
+        };
+
+        Ok(arm(param))
+    }
+}
+
+impl<U> Default for ReflectSpecializer<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> fmt::Debug for ReflectSpecializer<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReflectSpecializer")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
@@ -0,0 +1,118 @@
+use core::{any::TypeId, error::Error, fmt};
+
+/// Error registering an arm whose parameter type doesn't match its key
+///
+/// Returned by [`try_register_as()`](crate::DynSpecializer::try_register_as).
+///
+/// ```rust
+/// use std::any::TypeId;
+///
+/// use specializer::DynSpecializer;
+///
+/// let mut spec = DynSpecializer::new();
+/// let error = spec
+///     .try_register_as(TypeId::of::<i32>(), |int: u8| i32::from(int))
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "arm for `u8` registered under the TypeId of another type",
+/// );
+///
+/// spec.register(|int: i32| int * 2);
+///
+/// let error = spec
+///     .try_register_as(TypeId::of::<i32>(), |int: u8| i32::from(int))
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "arm for `u8` registered under the TypeId of `i32`",
+/// );
+///
+/// let error: Box<dyn std::error::Error> = Box::new(error);
+///
+/// assert!(error.source().is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterError {
+    expected: TypeId,
+    expected_name: Option<&'static str>,
+    found: TypeId,
+    found_name: &'static str,
+}
+
+impl RegisterError {
+    pub(crate) const fn new(
+        expected: TypeId,
+        expected_name: Option<&'static str>,
+        found: TypeId,
+        found_name: &'static str,
+    ) -> Self {
+        Self {
+            expected,
+            expected_name,
+            found,
+            found_name,
+        }
+    }
+
+    /// Return the [`TypeId`] the arm was registered under.
+    pub const fn expected(&self) -> TypeId {
+        self.expected
+    }
+
+    /// Return the name of the type the arm was registered under, if known.
+    ///
+    /// The name is only known if an arm is already registered for the
+    /// [`expected()`](RegisterError::expected) type.
+    pub const fn expected_name(&self) -> Option<&'static str> {
+        self.expected_name
+    }
+
+    /// Return the [`TypeId`] of the arm's actual parameter type.
+    pub const fn found(&self) -> TypeId {
+        self.found
+    }
+
+    /// Return the name of the arm's actual parameter type.
+    pub const fn found_name(&self) -> &'static str {
+        self.found_name
+    }
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "arm for `{}` registered under the TypeId of ",
+            self.found_name
+        )?;
+
+        match self.expected_name {
+            Some(expected) => write!(f, "`{expected}`"),
+            None => f.write_str("another type"),
+        }
+    }
+}
+
+impl Error for RegisterError {}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RegisterError {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self.expected_name {
+            Some(expected) => defmt::write!(
+                f,
+                "arm for {=str} registered under the TypeId of {=str}",
+                self.found_name,
+                expected,
+            ),
+            None => defmt::write!(
+                f,
+                "arm for {=str} registered under the TypeId of another type",
+                self.found_name,
+            ),
+        }
+    }
+}
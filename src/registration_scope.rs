@@ -0,0 +1,96 @@
+use core::{any::TypeId, ops::Deref};
+
+use crate::{DynSpecializer, RegisterError, StaticArm};
+
+/// Guard for temporary registrations in a [`DynSpecializer`]
+///
+/// Created with [`DynSpecializer::scope()`].  Dereferences to the registry
+/// for dispatch, and undoes any registrations made through it when dropped
+/// (including when unwinding from a panic).  Only registering is allowed
+/// while the scope is active, so everything it changes can be restored.
+#[derive(Debug)]
+pub struct RegistrationScope<'a, U> {
+    spec: &'a mut DynSpecializer<U>,
+}
+
+impl<'a, U> RegistrationScope<'a, U> {
+    pub(crate) fn new(spec: &'a mut DynSpecializer<U>) -> Self {
+        spec.begin_scope();
+
+        Self { spec }
+    }
+
+    /// Temporarily register an arm for parameter type `P`.
+    ///
+    /// See [`DynSpecializer::register()`].
+    #[track_caller]
+    pub fn register<P>(
+        &mut self,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+    {
+        self.spec.register(f);
+        self
+    }
+
+    /// Temporarily register an arm for parameter type `P` with a priority.
+    ///
+    /// See [`DynSpecializer::register_with_priority()`].
+    #[track_caller]
+    pub fn register_with_priority<P>(
+        &mut self,
+        priority: i32,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: 'static,
+    {
+        self.spec.register_with_priority(priority, f);
+        self
+    }
+
+    /// Temporarily register an arm for parameter type `P` under an externally
+    /// provided key.
+    ///
+    /// See [`DynSpecializer::try_register_as()`].
+    #[track_caller]
+    pub fn try_register_as<P>(
+        &mut self,
+        type_id: TypeId,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> Result<&mut Self, RegisterError>
+    where
+        P: 'static,
+    {
+        self.spec.try_register_as(type_id, f)?;
+
+        Ok(self)
+    }
+
+    /// Temporarily register each of the statically-constructed `arms`.
+    ///
+    /// See [`DynSpecializer::register_static()`].
+    pub fn register_static(&mut self, arms: &[StaticArm<U>]) -> &mut Self
+    where
+        U: 'static,
+    {
+        self.spec.register_static(arms);
+        self
+    }
+}
+
+impl<U> Deref for RegistrationScope<'_, U> {
+    type Target = DynSpecializer<U>;
+
+    fn deref(&self) -> &Self::Target {
+        self.spec
+    }
+}
+
+impl<U> Drop for RegistrationScope<'_, U> {
+    fn drop(&mut self) {
+        self.spec.end_scope();
+    }
+}
@@ -0,0 +1,99 @@
+use rkyv::{
+    Archive, Archived, boxed::ArchivedBox, string::ArchivedString,
+    vec::ArchivedVec,
+};
+
+/// Return `archived` as `&U` without deserializing, if `U` is the archived
+/// type of `T`.
+///
+/// This lets code generic over the requested type pass an archived value
+/// straight through when the caller asked for the archived form, and only
+/// deserialize otherwise.
+///
+/// ```rust
+/// use rkyv::{Archived, rancor::Error, vec::ArchivedVec};
+///
+/// let bytes = rkyv::to_bytes::<Error>(&vec![1u8, 2, 3]).unwrap();
+/// let archived = rkyv::access::<Archived<Vec<u8>>, Error>(&bytes).unwrap();
+/// let view = specializer::archived_as::<Vec<u8>, ArchivedVec<u8>>(archived);
+///
+/// assert_eq!(view.map(|vec| vec.as_slice()), Some(&[1, 2, 3][..]));
+/// assert!(specializer::archived_as::<Vec<u8>, Vec<u8>>(archived).is_none());
+/// ```
+pub fn archived_as<T, U>(archived: &Archived<T>) -> Option<&U>
+where
+    T: Archive,
+    Archived<T>: 'static,
+    U: 'static,
+{
+    crate::cast_identity_ref(archived)
+}
+
+/// Return the bytes of an archived byte buffer without copying.
+///
+/// Recognizes the archived forms of `Vec<u8>`, `Box<[u8]>`, and `String`,
+/// and byte arrays.  Returns `None` for any other archived type.
+///
+/// ```rust
+/// use rkyv::{Archived, rancor::Error};
+///
+/// let bytes = rkyv::to_bytes::<Error>(&"Hello".to_owned()).unwrap();
+/// let archived = rkyv::access::<Archived<String>, Error>(&bytes).unwrap();
+///
+/// assert_eq!(specializer::archived_bytes(archived), Some(&b"Hello"[..]));
+/// assert_eq!(specializer::archived_bytes(&true), None);
+/// ```
+pub fn archived_bytes<A>(archived: &A) -> Option<&[u8]>
+where
+    A: 'static,
+{
+    if let Some(vec) = crate::cast_identity_ref::<A, ArchivedVec<u8>>(archived)
+    {
+        return Some(vec.as_slice());
+    }
+
+    if let Some(boxed) =
+        crate::cast_identity_ref::<A, ArchivedBox<[u8]>>(archived)
+    {
+        return Some(boxed.get());
+    }
+
+    if let Some(string) = archived_str(archived) {
+        return Some(string.as_bytes());
+    }
+
+    None
+}
+
+/// Return the string of an archived string type without copying.
+///
+/// Recognizes the archived forms of `String` and `Box<str>`.  Returns `None`
+/// for any other archived type.
+///
+/// ```rust
+/// use rkyv::{Archived, rancor::Error};
+///
+/// let bytes = rkyv::to_bytes::<Error>(&"Hello".to_owned()).unwrap();
+/// let archived = rkyv::access::<Archived<String>, Error>(&bytes).unwrap();
+///
+/// assert_eq!(specializer::archived_str(archived), Some("Hello"));
+/// assert_eq!(specializer::archived_str(&0u8), None);
+/// ```
+pub fn archived_str<A>(archived: &A) -> Option<&str>
+where
+    A: 'static,
+{
+    if let Some(string) =
+        crate::cast_identity_ref::<A, ArchivedString>(archived)
+    {
+        return Some(string.as_str());
+    }
+
+    if let Some(boxed) =
+        crate::cast_identity_ref::<A, ArchivedBox<str>>(archived)
+    {
+        return Some(boxed.get());
+    }
+
+    None
+}
@@ -0,0 +1,49 @@
+use crate::CastIdentityBorrowed;
+
+/// Adapter for positions of borrowed composites that never vary
+///
+/// [`CastIdentityBorrowed`] is implemented from `Same<T>` to itself only,
+/// without requiring `T: 'static`, so that a value of a known concrete type
+/// can be passed to the borrowed specializers alongside the specialized
+/// borrows, like in `(&'a mut T, Same<Config>)`.
+///
+/// ```rust
+/// use specializer::{Same, SpecializerBorrowedParam};
+///
+/// struct Config<'a> {
+///     separator: &'a str,
+/// }
+///
+/// fn join<'a, T>(items: &'a Vec<T>, config: Config<'a>) -> String
+/// where
+///     T: 'static,
+/// {
+///     type Params<'a> = (&'a Vec<String>, Same<Config<'a>>);
+///
+///     SpecializerBorrowedParam::new((items, Same(config)), |_| String::new())
+///         .specialize_param(|(items, Same(config)): Params<'a>| {
+///             items.join(config.separator)
+///         })
+///         .run()
+/// }
+///
+/// let separator = ", ".to_owned();
+/// let strings = vec!["a".to_owned(), "b".to_owned()];
+///
+/// assert_eq!(join(&strings, Config { separator: &separator }), "a, b");
+/// assert_eq!(join(&vec![1, 2], Config { separator: &separator }), "");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Same<T>(pub T);
+
+impl<T> CastIdentityBorrowed<Same<T>> for Same<T> {
+    #[inline(always)]
+    fn cast_identity(self) -> Option<Same<T>> {
+        Some(self)
+    }
+
+    #[inline(always)]
+    fn is_same() -> bool {
+        true
+    }
+}
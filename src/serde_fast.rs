@@ -0,0 +1,338 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+/// Largest number of bytes to preallocate from an untrusted sequence length
+#[cfg(feature = "alloc")]
+const MAX_PREALLOCATION: usize = 4096;
+
+/// Arms run for byte and string types when serializing
+///
+/// Implemented for every [`Serializer`], calling
+/// [`Serializer::serialize_bytes()`] and [`Serializer::serialize_str()`], so
+/// format authors can reuse the classic fast paths with
+/// [`dispatch_serialize()`], or implement their own set.
+///
+/// ```rust
+/// use specializer::SerializeArms;
+///
+/// struct Describe;
+///
+/// impl SerializeArms<String> for Describe {
+///     fn bytes(self, bytes: &[u8]) -> String {
+///         format!("{} bytes", bytes.len())
+///     }
+///
+///     fn str(self, string: &str) -> String {
+///         format!("string {string:?}")
+///     }
+/// }
+///
+/// fn describe<T: 'static>(value: &T) -> String {
+///     specializer::dispatch_serialize(value, Describe)
+///         .unwrap_or_else(|_| "other".to_owned())
+/// }
+///
+/// assert_eq!(describe(&Vec::from([1u8, 2, 3])), "3 bytes");
+/// assert_eq!(describe(&"Hello"), r#"string "Hello""#);
+/// assert_eq!(describe(&Vec::from([1u16, 2, 3])), "other");
+/// ```
+pub trait SerializeArms<U>: Sized {
+    /// Run the arm for byte containers.
+    fn bytes(self, bytes: &[u8]) -> U;
+
+    /// Run the arm for string types.
+    fn str(self, string: &str) -> U;
+}
+
+impl<S> SerializeArms<Result<S::Ok, S::Error>> for S
+where
+    S: Serializer,
+{
+    #[inline(always)]
+    fn bytes(self, bytes: &[u8]) -> Result<S::Ok, S::Error> {
+        self.serialize_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn str(self, string: &str) -> Result<S::Ok, S::Error> {
+        self.serialize_str(string)
+    }
+}
+
+/// Arms run for byte and string types when deserializing
+///
+/// Implemented for every [`Deserializer`], calling
+/// [`Deserializer::deserialize_byte_buf()`] and
+/// [`Deserializer::deserialize_string()`], so format authors can reuse the
+/// classic fast paths with [`dispatch_deserialize()`], or implement their own
+/// set.
+///
+/// ```rust
+/// use specializer::DeserializeArms;
+///
+/// struct Fixed;
+///
+/// impl DeserializeArms<()> for Fixed {
+///     fn byte_buf(self) -> Result<Vec<u8>, ()> {
+///         Ok(Vec::from(*b"bytes"))
+///     }
+///
+///     fn string(self) -> Result<String, ()> {
+///         Ok("string".to_owned())
+///     }
+/// }
+///
+/// let bytes = specializer::dispatch_deserialize::<Box<[u8]>, _, _>(Fixed);
+/// let int = specializer::dispatch_deserialize::<u32, _, _>(Fixed);
+///
+/// assert_eq!(bytes.ok(), Some(Ok(b"bytes".as_slice().into())));
+/// assert!(int.is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub trait DeserializeArms<E>: Sized {
+    /// Run the arm for byte containers.
+    fn byte_buf(self) -> Result<Vec<u8>, E>;
+
+    /// Run the arm for string types.
+    fn string(self) -> Result<String, E>;
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, D> DeserializeArms<D::Error> for D
+where
+    D: Deserializer<'de>,
+{
+    #[inline(always)]
+    fn byte_buf(self) -> Result<Vec<u8>, D::Error> {
+        self.deserialize_byte_buf(BytesVisitor)
+    }
+
+    #[inline(always)]
+    fn string(self) -> Result<String, D::Error> {
+        String::deserialize(self)
+    }
+}
+
+/// Run the arm of `arms` for the type of `value` if it's a byte or string
+/// type.
+///
+/// Byte containers (`[u8]`, `&'static [u8]`, `Vec<u8>`, and `Box<[u8]>`) are
+/// passed to [`SerializeArms::bytes()`], and string types (`str`,
+/// `&'static str`, `String`, and `Box<str>`) to [`SerializeArms::str()`].
+/// Returns `Err(arms)` for all other types.
+#[inline]
+pub fn dispatch_serialize<T, U, A>(value: &T, arms: A) -> Result<U, A>
+where
+    T: ?Sized + 'static,
+    A: SerializeArms<U>,
+{
+    if let Some(bytes) = crate::cast_identity_ref::<T, [u8]>(value) {
+        return Ok(arms.bytes(bytes));
+    }
+
+    if let Some(bytes) = crate::cast_identity_ref::<T, &[u8]>(value) {
+        return Ok(arms.bytes(bytes));
+    }
+
+    if let Some(string) = crate::cast_identity_ref::<T, str>(value) {
+        return Ok(arms.str(string));
+    }
+
+    if let Some(string) = crate::cast_identity_ref::<T, &str>(value) {
+        return Ok(arms.str(string));
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        if let Some(bytes) = crate::cast_identity_ref::<T, Vec<u8>>(value) {
+            return Ok(arms.bytes(bytes));
+        }
+
+        if let Some(bytes) = crate::cast_identity_ref::<T, Box<[u8]>>(value) {
+            return Ok(arms.bytes(bytes));
+        }
+
+        if let Some(string) = crate::cast_identity_ref::<T, String>(value) {
+            return Ok(arms.str(string));
+        }
+
+        if let Some(string) = crate::cast_identity_ref::<T, Box<str>>(value) {
+            return Ok(arms.str(string));
+        }
+    }
+
+    Err(arms)
+}
+
+/// Run the arm of `arms` for `T` if it's an owned byte or string type.
+///
+/// `Vec<u8>` and `Box<[u8]>` are produced by [`DeserializeArms::byte_buf()`],
+/// and `String` and `Box<str>` by [`DeserializeArms::string()`].  Returns
+/// `Err(arms)` for all other types.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn dispatch_deserialize<T, E, A>(arms: A) -> Result<Result<T, E>, A>
+where
+    T: 'static,
+    A: DeserializeArms<E>,
+{
+    // Only called once checked to be the same type, so it can't fail
+    fn cast<V: 'static, T: 'static>(value: V) -> T {
+        crate::cast_identity(value).unwrap()
+    }
+
+    if crate::api::type_eq::<T, Vec<u8>>() {
+        return Ok(arms.byte_buf().map(cast));
+    }
+
+    if crate::api::type_eq::<T, Box<[u8]>>() {
+        return Ok(arms.byte_buf().map(|b| cast(b.into_boxed_slice())));
+    }
+
+    if crate::api::type_eq::<T, String>() {
+        return Ok(arms.string().map(cast));
+    }
+
+    if crate::api::type_eq::<T, Box<str>>() {
+        return Ok(arms.string().map(|s| cast(s.into_boxed_str())));
+    }
+
+    Err(arms)
+}
+
+/// Serialize `value`, taking the serializer's fast path for byte and string
+/// types.
+///
+/// Byte containers (`&'static [u8]`, `Vec<u8>`, and `Box<[u8]>`) are
+/// serialized with [`Serializer::serialize_bytes()`] rather than as a
+/// sequence of integers, and string types (`&'static str`, `String`, and
+/// `Box<str>`) are passed directly to [`Serializer::serialize_str()`].  All
+/// other types use their [`Serialize`] implementation.  See
+/// [`dispatch_serialize()`].
+///
+/// Can be used with `#[serde(serialize_with = "specializer::serialize_fast")]`.
+///
+/// ```rust
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Packet {
+///     #[serde(serialize_with = "specializer::serialize_fast")]
+///     payload: Vec<u8>,
+/// }
+///
+/// let packet = Packet {
+///     payload: Vec::from([1, 2, 3]),
+/// };
+/// let json = serde_json::to_string(&packet);
+///
+/// // serde_json encodes bytes as an array either way
+/// assert_eq!(json.unwrap(), r#"{"payload":[1,2,3]}"#);
+/// ```
+pub fn serialize_fast<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + 'static,
+    S: Serializer,
+{
+    dispatch_serialize(value, serializer)
+        .unwrap_or_else(|serializer| value.serialize(serializer))
+}
+
+/// Deserialize a `T`, taking the deserializer's fast path for byte and string
+/// types.
+///
+/// `Vec<u8>` and `Box<[u8]>` are deserialized with
+/// [`Deserializer::deserialize_byte_buf()`] rather than as a sequence of
+/// integers, and `String` and `Box<str>` with
+/// [`Deserializer::deserialize_string()`].  All other types use their
+/// [`Deserialize`] implementation.  See [`dispatch_deserialize()`].
+///
+/// Can be used with
+/// `#[serde(deserialize_with = "specializer::deserialize_fast")]`.
+///
+/// ```rust
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Packet {
+///     #[serde(deserialize_with = "specializer::deserialize_fast")]
+///     payload: Vec<u8>,
+///     #[serde(deserialize_with = "specializer::deserialize_fast")]
+///     name: String,
+/// }
+///
+/// let packet: Packet =
+///     serde_json::from_str(r#"{"payload":"abc","name":"ferris"}"#).unwrap();
+///
+/// assert_eq!(packet.payload, b"abc");
+/// assert_eq!(packet.name, "ferris");
+/// ```
+///
+/// Bytes aren't accepted from string input (unless the format itself encodes
+/// bytes as strings, like `serde_json` does above):
+///
+/// ```rust
+/// use serde::de::{IntoDeserializer, value::Error};
+///
+/// let string = "abc".into_deserializer();
+/// let bytes: Result<Vec<u8>, Error> = specializer::deserialize_fast(string);
+///
+/// assert!(bytes.is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn deserialize_fast<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + 'static,
+    D: Deserializer<'de>,
+{
+    dispatch_deserialize(deserializer)
+        .unwrap_or_else(|deserializer| T::deserialize(deserializer))
+}
+
+/// Visitor accepting byte input, or a sequence of bytes, as a `Vec<u8>`
+#[cfg(feature = "alloc")]
+struct BytesVisitor;
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(bytes)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        // The length hint comes from the input, so don't trust it too far
+        let hint = seq.size_hint().unwrap_or(0);
+        let mut bytes = Vec::with_capacity(hint.min(MAX_PREALLOCATION));
+
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(bytes)
+    }
+}
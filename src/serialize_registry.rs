@@ -0,0 +1,166 @@
+use alloc::boxed::Box;
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+use serde::{Serialize, Serializer, ser::Error};
+
+use crate::small_map::SmallMap;
+
+type Arm = Box<
+    dyn for<'a> Fn(&'a dyn Any) -> Option<&'a dyn erased_serde::Serialize>
+        + Send
+        + Sync,
+>;
+
+/// Runtime registry of serializers for type-erased values
+///
+/// Arms are registered per type at runtime, and look up the
+/// [`erased_serde::Serialize`](https://docs.rs/erased-serde) implementation
+/// for a `&dyn Any` by its runtime type, so values that have already been
+/// type-erased can still be serialized.
+///
+/// ```rust
+/// use std::any::Any;
+///
+/// use specializer::SerializeRegistry;
+///
+/// struct Wrapper {
+///     inner: Vec<u8>,
+/// }
+///
+/// let mut registry = SerializeRegistry::new();
+///
+/// registry
+///     .register::<i32>()
+///     .register::<String>()
+///     .register_with(|wrapper: &Wrapper| &wrapper.inner);
+///
+/// let values: [Box<dyn Any>; 3] = [
+///     Box::new(3i32),
+///     Box::new("Hi".to_owned()),
+///     Box::new(Wrapper { inner: vec![1, 2] }),
+/// ];
+/// let json = values
+///     .iter()
+///     .map(|value| {
+///         let mut json = Vec::new();
+///         let mut serializer = serde_json::Serializer::new(&mut json);
+///
+///         registry.serialize(&**value, &mut serializer).unwrap();
+///         String::from_utf8(json).unwrap()
+///     })
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(json, ["3", "\"Hi\"", "[1,2]"]);
+/// assert!(registry.serialize(&3u8, serde_json::value::Serializer).is_err());
+/// ```
+pub struct SerializeRegistry {
+    /// Serializers, with the name of their type
+    arms: SmallMap<TypeId, (&'static str, Arm)>,
+}
+
+impl SerializeRegistry {
+    /// Create a new registry without any registered serializers.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+        }
+    }
+
+    /// Register the [`Serialize`] implementation of type `P`.
+    ///
+    /// Registering a second serializer for the same type replaces the first.
+    pub fn register<P>(&mut self) -> &mut Self
+    where
+        P: Serialize + 'static,
+    {
+        self.register_with(|param: &P| param)
+    }
+
+    /// Register a serializer for type `P`, which serializes the value
+    /// returned from `f`.
+    ///
+    /// Registering a second serializer for the same type replaces the first.
+    pub fn register_with<P, S>(&mut self, f: fn(&P) -> &S) -> &mut Self
+    where
+        P: 'static,
+        S: Serialize + 'static,
+    {
+        let arm: Arm = Box::new(move |any| {
+            let value: &dyn erased_serde::Serialize = f(any.downcast_ref()?);
+
+            Some(value)
+        });
+
+        self.arms
+            .insert(TypeId::of::<P>(), (core::any::type_name::<P>(), arm));
+        self
+    }
+
+    /// Return true if a serializer is registered for type `P`.
+    pub fn contains<P>(&self) -> bool
+    where
+        P: 'static,
+    {
+        self.arms.contains_key(&TypeId::of::<P>())
+    }
+
+    /// Return the number of types with registered serializers.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no serializers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Look up the serializer for the runtime type of `value`.
+    ///
+    /// Returns `None` if no serializer is registered for the type.
+    pub fn get<'a>(
+        &self,
+        value: &'a dyn Any,
+    ) -> Option<&'a dyn erased_serde::Serialize> {
+        let arm = self.arms.get(&value.type_id());
+        let name = arm.map_or(crate::trace::UNKNOWN, |(name, _)| name);
+        let (_, arm) = crate::trace::found_named(name, arm)?;
+
+        arm(value)
+    }
+
+    /// Serialize `value` with the serializer registered for its runtime type.
+    ///
+    /// Fails with a custom error if no serializer is registered for the type.
+    pub fn serialize<S>(
+        &self,
+        value: &dyn Any,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some(value) = self.get(value) else {
+            return Err(S::Error::custom("no serializer registered for type"));
+        };
+
+        value.serialize(serializer)
+    }
+}
+
+impl Default for SerializeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SerializeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerializeRegistry")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(not(feature = "hashbrown"))]
+use alloc::collections::BTreeMap;
+use core::{hash::Hash, mem};
+
+/// Number of entries stored inline before spilling to a [`Map`]
+const INLINE: usize = 8;
+
+/// Map used once a registry outgrows its inline storage
+#[cfg(not(feature = "hashbrown"))]
+pub(crate) type Map<K, V> = BTreeMap<K, V>;
+
+/// Map used once a registry outgrows its inline storage
+#[cfg(feature = "hashbrown")]
+pub(crate) type Map<K, V> = hashbrown::HashMap<K, V>;
+
+/// Map optimized for a small number of entries
+///
+/// Up to [`INLINE`] entries are stored in an inline array and looked up with a
+/// linear scan, which is faster and more cache-friendly than a tree for tiny
+/// dispatch tables; after that, entries move to a [`Map`].
+pub(crate) enum SmallMap<K, V> {
+    Inline(usize, [Option<(K, V)>; INLINE]),
+    Spilled(Map<K, V>),
+}
+
+impl<K, V> SmallMap<K, V>
+where
+    K: Copy + Ord + Hash,
+{
+    pub(crate) const fn new() -> Self {
+        Self::Inline(0, [const { None }; INLINE])
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Inline(len, _) => *len,
+            Self::Spilled(map) => map.len(),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<&V> {
+        match self {
+            Self::Inline(len, entries) => entries[..*len]
+                .iter()
+                .flatten()
+                .find_map(|(k, v)| (k == key).then_some(v)),
+            Self::Spilled(map) => map.get(key),
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self {
+            Self::Inline(len, entries) => entries[..*len]
+                .iter_mut()
+                .flatten()
+                .find_map(|(k, v)| (k == key).then_some(v)),
+            Self::Spilled(map) => map.get_mut(key),
+        }
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Insert a value, returning the previous value for the key if any.
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(mem::replace(old, value));
+        }
+
+        match self {
+            Self::Inline(len, entries) if *len < INLINE => {
+                entries[*len] = Some((key, value));
+                *len += 1;
+            }
+            Self::Inline(_, entries) => {
+                let mut map: Map<K, V> =
+                    entries.iter_mut().flat_map(Option::take).collect();
+
+                map.insert(key, value);
+                *self = Self::Spilled(map);
+            }
+            Self::Spilled(map) => {
+                map.insert(key, value);
+            }
+        }
+
+        None
+    }
+
+    /// Return the value for a key, inserting one from `f` if not present.
+    pub(crate) fn get_or_insert_with(
+        &mut self,
+        key: K,
+        f: impl FnOnce() -> V,
+    ) -> &mut V {
+        if !self.contains_key(&key) {
+            self.insert(key, f());
+        }
+
+        self.get_mut(&key).unwrap()
+    }
+
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        match self {
+            Self::Inline(len, entries) => {
+                let index = entries[..*len]
+                    .iter()
+                    .position(|entry| entry.as_ref().unwrap().0 == *key)?;
+
+                *len -= 1;
+                entries.swap(index, *len);
+                entries[*len].take().map(|(_, v)| v)
+            }
+            Self::Spilled(map) => map.remove(key),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let (inline, spilled) = match self {
+            Self::Inline(len, entries) => (Some(&entries[..*len]), None),
+            Self::Spilled(map) => (None, Some(map)),
+        };
+        let inline =
+            inline.into_iter().flatten().flatten().map(|(k, v)| (k, v));
+
+        inline.chain(spilled.into_iter().flatten())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Specializer, SpecializerBorrowedParam};
+
+/// Extension trait for specializing on any `'static` value
+pub trait Specialize: Sized + 'static {
+    /// Start a [`Specializer`] on this value with a fallback function.
+    ///
+    /// ```rust
+    /// use specializer::Specialize;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     ty.specialize(|_| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .specialize_param(|string: String| string)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "int 3");
+    /// assert_eq!(describe("Hello".to_owned()), "Hello");
+    /// assert_eq!(describe(()), "unknown");
+    /// ```
+    #[inline(always)]
+    fn specialize<U, F>(self, f: F) -> Specializer<Self, U, F>
+    where
+        F: FnOnce(Self) -> U,
+        U: 'static,
+    {
+        Specializer::new(self, f)
+    }
+
+    /// Start a [`SpecializerBorrowedParam`] on a shared reference to this
+    /// value with a fallback function.
+    ///
+    /// ```rust
+    /// use specializer::Specialize;
+    ///
+    /// fn len<T: 'static>(ty: &T) -> usize {
+    ///     ty.specialize_ref(|_| 0)
+    ///         .specialize_param(|string: &String| string.len())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(len(&"Hello".to_owned()), 5);
+    /// assert_eq!(len(&3), 0);
+    /// ```
+    #[inline(always)]
+    fn specialize_ref<U, F>(
+        &self,
+        f: F,
+    ) -> SpecializerBorrowedParam<&Self, U, F>
+    where
+        F: FnOnce(&Self) -> U,
+        U: 'static,
+    {
+        SpecializerBorrowedParam::new(self, f)
+    }
+
+    /// Start a [`SpecializerBorrowedParam`] on a mutable reference to this
+    /// value with a fallback function.
+    ///
+    /// ```rust
+    /// use specializer::Specialize;
+    ///
+    /// fn clear<T: 'static>(ty: &mut T) -> bool {
+    ///     ty.specialize_mut(|_| false)
+    ///         .specialize_param(|string: &mut String| {
+    ///             string.clear();
+    ///             true
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// let mut string = "Hello".to_owned();
+    ///
+    /// assert!(clear(&mut string));
+    /// assert!(string.is_empty());
+    /// assert!(!clear(&mut 3));
+    /// ```
+    #[inline(always)]
+    fn specialize_mut<U, F>(
+        &mut self,
+        f: F,
+    ) -> SpecializerBorrowedParam<&mut Self, U, F>
+    where
+        F: FnOnce(&mut Self) -> U,
+        U: 'static,
+    {
+        SpecializerBorrowedParam::new(self, f)
+    }
+}
+
+impl<T> Specialize for T where T: 'static {}
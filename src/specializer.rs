@@ -1,8 +1,147 @@
-use core::{any::TypeId, convert, marker::PhantomData};
+use core::{convert, marker::PhantomData, ops::ControlFlow};
+
+use crate::{
+    Arms, CastIdentityBorrowed, SpecializerBorrowed, SpecializerBorrowedParam,
+    SpecializerBorrowedReturn, SpecializerChain, SpecializerOption, TypeGroup,
+};
+
+type NoFallback<T, U> = fn(T) -> Result<U, T>;
 
 /// Specialized behavior runner (Owned -> Owned)
 #[derive(Debug)]
-pub struct Specializer<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct Specializer<T, U, F>(T, F, PhantomData<fn(T) -> U>, bool, bool);
+
+impl<T, U> Specializer<T, U, fn(T) -> U>
+where
+    T: 'static,
+    U: 'static,
+{
+    /// Start building a specializer from its arms, passing the fallback
+    /// function last with [`fallback()`](SpecializerOption::fallback).
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     Specializer::for_value(ty)
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .specialize_param(|string: String| string)
+    ///         .fallback(|_| "unknown".to_owned())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "int 3");
+    /// assert_eq!(describe("Hello".to_owned()), "Hello");
+    /// assert_eq!(describe(()), "unknown");
+    /// ```
+    #[inline(always)]
+    pub const fn for_value(
+        params: T,
+    ) -> SpecializerOption<T, U, NoFallback<T, U>> {
+        SpecializerOption::new(params)
+    }
+
+    /// Create a new specializer with a fallback function that is never
+    /// inlined and marked as unlikely to be called.
+    ///
+    /// Use this when the fallback is large and the specialized arms are
+    /// expected to be hit, so that the fallback doesn't bloat every
+    /// monomorphization of the fast paths.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn sum<T>(values: &[T]) -> f64
+    /// where
+    ///     T: 'static + Copy + Into<f64>,
+    /// {
+    ///     let scalar = |vals: Vec<T>| vals.into_iter().map(Into::into).sum();
+    ///
+    ///     Specializer::new_cold(values.to_vec(), scalar)
+    ///         .specialize_param(|values: Vec<f64>| values.iter().sum())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(sum(&[1.0f64, 2.0]), 3.0);
+    /// assert_eq!(sum(&[1.0f32, 2.0]), 3.0);
+    /// ```
+    #[inline(always)]
+    pub fn new_cold(
+        params: T,
+        f: impl FnOnce(T) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        Specializer::new(params, move |t| cold(f, t))
+    }
+
+    /// Create a new specializer on a shared reference with a fallback
+    /// function.
+    ///
+    /// Arms take `&P`; see [`SpecializerBorrowedParam`].
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn len<T: 'static>(ty: &T) -> usize {
+    ///     Specializer::by_ref(ty, |_| 0)
+    ///         .specialize_param(|string: &String| string.len())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(len(&"Hello".to_owned()), 5);
+    /// assert_eq!(len(&3), 0);
+    /// ```
+    #[inline(always)]
+    pub const fn by_ref<'a, F>(
+        params: &'a T,
+        f: F,
+    ) -> SpecializerBorrowedParam<&'a T, U, F>
+    where
+        F: FnOnce(&'a T) -> U,
+    {
+        SpecializerBorrowedParam::new(params, f)
+    }
+
+    /// Create a new specializer on a mutable reference with a fallback
+    /// function.
+    ///
+    /// Arms take `&mut P`; see [`SpecializerBorrowedParam`].
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn double<T: 'static>(ty: &mut T) -> bool {
+    ///     Specializer::by_mut(ty, |_| false)
+    ///         .specialize_param(|int: &mut i32| {
+    ///             *int *= 2;
+    ///             true
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// let mut int = 3;
+    ///
+    /// assert!(double(&mut int));
+    /// assert_eq!(int, 6);
+    /// assert!(!double(&mut 3u8));
+    /// ```
+    #[inline(always)]
+    pub const fn by_mut<'a, F>(
+        params: &'a mut T,
+        f: F,
+    ) -> SpecializerBorrowedParam<&'a mut T, U, F>
+    where
+        F: FnOnce(&'a mut T) -> U,
+    {
+        SpecializerBorrowedParam::new(params, f)
+    }
+}
+
+/// Call the fallback function out of line.
+#[cold]
+#[inline(never)]
+fn cold<T, U>(f: impl FnOnce(T) -> U, t: T) -> U {
+    f(t)
+}
 
 impl<T, U, F> Specializer<T, U, F>
 where
@@ -13,7 +152,7 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -45,20 +184,23 @@ where
         P: 'static,
         R: 'static,
     {
-        let Specializer(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
-            if TypeId::of::<T>() == TypeId::of::<P>()
-                && TypeId::of::<U>() == TypeId::of::<R>()
-            {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
 
                 return crate::cast_identity::<R, U>(f(param)).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        Specializer(ty, f, phantom_data)
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        Specializer(ty, f, phantom_data, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -99,11 +241,10 @@ where
         P: 'static,
         R: 'static,
     {
-        let Specializer(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
-            if TypeId::of::<T>() == TypeId::of::<P>()
-                && TypeId::of::<U>() == TypeId::of::<R>()
-            {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
                 let param = crate::cast_identity::<P, T>(p(param)).unwrap();
                 let ret = crate::cast_identity::<U, R>(f(param)).unwrap();
@@ -111,10 +252,223 @@ where
                 return crate::cast_identity::<R, U>(r(ret)).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on the layout of the parameter of the closure.
+    ///
+    /// Unlike the other arms, this doesn't require the parameter to be the
+    /// same type as `P`: the arm runs for any plain old data type with the
+    /// same size as `P`, which is reinterpreted as `P` with
+    /// [`cast_layout()`](crate::cast_layout).
+    ///
+    /// ```rust
+    /// use bytemuck::Pod;
+    /// use specializer::Specializer;
+    ///
+    /// fn checksum<T: Pod>(ty: T) -> u32 {
+    ///     let bytes = |ty: T| bytemuck::bytes_of(&ty).to_vec();
+    ///     let sum = |ty| bytes(ty).into_iter().map(u32::from).sum();
+    ///
+    ///     Specializer::new(ty, sum)
+    ///         .specialize_layout(|word: u32| word.count_ones())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(checksum([1u8, 2, 3]), 6);
+    /// assert_eq!(checksum(0b1011u32), 3);
+    /// assert_eq!(checksum([0b11u8, 0, 0, 0]), 2);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn specialize_layout<P>(
+        self,
+        f: impl FnOnce(P) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        T: bytemuck::Pod,
+        P: bytemuck::Pod,
+    {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if size_of::<T>() == size_of::<P>() {
+                crate::trace::taken::<T, P>();
+
+                return f(crate::cast_layout::<T, P>(t).unwrap());
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        let matched = matched || size_of::<T>() == size_of::<P>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on any primitive integer type with one [`IntegerArm`].
+    ///
+    /// See [`IntegerArm`] for an example.
+    ///
+    /// [`IntegerArm`]: crate::IntegerArm
+    #[cfg(feature = "num-traits")]
+    #[inline]
+    pub fn specialize_integer(
+        self,
+        arm: impl crate::IntegerArm<U>,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| {
+            crate::trace::dispatched(crate::dispatch_integer(t, arm), armed)
+                .unwrap_or_else(fallback)
+        };
+
+        let matched = matched || crate::is_integer::<T>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on any primitive signed integer type with one
+    /// [`SignedArm`].
+    ///
+    /// [`SignedArm`]: crate::SignedArm
+    #[cfg(feature = "num-traits")]
+    #[inline]
+    pub fn specialize_signed(
+        self,
+        arm: impl crate::SignedArm<U>,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| {
+            crate::trace::dispatched(crate::dispatch_signed(t, arm), armed)
+                .unwrap_or_else(fallback)
+        };
+
+        let matched = matched || crate::is_signed::<T>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on any primitive floating point type with one
+    /// [`FloatArm`].
+    ///
+    /// [`FloatArm`]: crate::FloatArm
+    #[cfg(feature = "num-traits")]
+    #[inline]
+    pub fn specialize_float(
+        self,
+        arm: impl crate::FloatArm<U>,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| {
+            crate::trace::dispatched(crate::dispatch_float(t, arm), armed)
+                .unwrap_or_else(fallback)
+        };
+
+        let matched = matched || crate::is_float::<T>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on any of the types in group `G`, converted into the
+    /// parameter of the closure.
+    ///
+    /// For one generic body over every primitive integer type, see also
+    /// `specialize_integer()` (requires the `num-traits` feature).
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn widened<T: 'static>(ty: T) -> Option<i64> {
+    ///     Specializer::new(ty, |_| None)
+    ///         .specialize_all::<(i8, i16, i32, i64), _>(|int: i64| Some(int))
+    ///         .specialize_all::<(u8, u16, u32), _>(|int: u32| {
+    ///             Some(-i64::from(int))
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(widened(3i8), Some(3));
+    /// assert_eq!(widened(3i32), Some(3));
+    /// assert_eq!(widened(3u16), Some(-3));
+    /// assert_eq!(widened(3u64), None);
+    /// ```
+    #[inline]
+    pub fn specialize_all<G, B>(
+        self,
+        f: impl FnOnce(B) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        G: TypeGroup<B> + 'static,
+    {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if G::contains::<T>() {
+                crate::trace::taken::<T, G>();
+
+                return f(G::convert(t).ok().unwrap());
+            }
+
+            crate::trace::missed::<T, G>(armed);
+
             fallback(t)
         };
 
-        Specializer(ty, f, phantom_data)
+        let matched = matched || G::contains::<T>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on the parameters of several closures at once, consulting
+    /// them in order.
+    ///
+    /// Unlike chained [`specialize_param()`](Specializer::specialize_param)
+    /// calls, this adds one level of nesting to the composed closure for all
+    /// of the arms.  See [`Arms`].
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     Specializer::new(ty, |_| "unknown".to_owned())
+    ///         .specialize_many((
+    ///             |int: i32| format!("int {int}"),
+    ///             |string: String| string,
+    ///             |(): ()| "nothing".to_owned(),
+    ///         ))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "int 3");
+    /// assert_eq!(describe("Hello".to_owned()), "Hello");
+    /// assert_eq!(describe(()), "nothing");
+    /// assert_eq!(describe(true), "unknown");
+    /// ```
+    #[inline]
+    pub fn specialize_many<A, P>(
+        self,
+        arms: A,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        A: Arms<T, U, P>,
+    {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            crate::trace::dispatched(arms.dispatch(t), armed)
+                .unwrap_or_else(fallback)
+        };
+
+        let matched = matched || A::accepts();
+
+        Specializer(ty, f, phantom_data, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -149,6 +503,95 @@ where
         self.specialize::<P, U>(f)
     }
 
+    /// Specialize on the parameter of the closure, which only borrows it.
+    ///
+    /// The parameter is dropped after the closure returns.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn len<T: 'static>(ty: T) -> usize {
+    ///     Specializer::new(ty, |_| 0)
+    ///         .specialize_param_ref(|string: &String| string.len())
+    ///         .specialize_param_ref(Vec::<u8>::len)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(len("Hello".to_owned()), 5);
+    /// assert_eq!(len(vec![1u8, 2]), 2);
+    /// assert_eq!(len(3), 0);
+    /// ```
+    #[inline]
+    pub fn specialize_param_ref<P>(
+        self,
+        f: impl FnOnce(&P) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(move |p: P| f(&p))
+    }
+
+    /// Specialize on the parameter of the closure, converting its return
+    /// value into `U`.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn widen<T, U>(ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + Default + From<u8> + From<bool>,
+    /// {
+    ///     Specializer::new(ty, |_| U::default())
+    ///         .specialize_into(|int: u8| int.saturating_mul(2))
+    ///         .specialize_into(|string: String| string.is_empty())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(widen::<_, u32>(200u8), 255);
+    /// assert_eq!(widen::<_, u32>(String::new()), 1);
+    /// assert_eq!(widen::<_, u32>(3i64), 0);
+    /// ```
+    #[inline]
+    pub fn specialize_into<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        P: 'static,
+        R: Into<U>,
+    {
+        self.specialize_param(move |p: P| f(p).into())
+    }
+
+    /// Specialize on the parameter type `P`, returning `value`.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> &'static str {
+    ///     Specializer::new(ty, |_| "something")
+    ///         .specialize_value::<()>("nothing")
+    ///         .specialize_value::<bool>("a flag")
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(()), "nothing");
+    /// assert_eq!(describe(true), "a flag");
+    /// assert_eq!(describe(3), "something");
+    /// ```
+    #[inline]
+    pub fn specialize_value<P>(
+        self,
+        value: U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(move |_: P| value)
+    }
+
     /// Specialize on the return type of the closure.
     ///
     /// ```rust
@@ -181,6 +624,31 @@ where
         self.specialize::<T, R>(f)
     }
 
+    /// Specialize on the return type `R`, returning `R::default()`.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn parse<T: 'static>(string: &str) -> T {
+    ///     Specializer::new(string.to_owned(), |_| panic!("unsupported type"))
+    ///         .specialize_return(|string| -> u32 { string.parse().unwrap() })
+    ///         .specialize_default_return::<Vec<u8>>()
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(parse::<u32>("42"), 42);
+    /// assert_eq!(parse::<Vec<u8>>("42"), []);
+    /// ```
+    #[inline]
+    pub fn specialize_default_return<R>(
+        self,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        R: 'static + Default,
+    {
+        self.specialize_return(|_| R::default())
+    }
+
     /// Specialize on the parameter and the return type of the closure, mapping
     /// the parameter.
     ///
@@ -251,9 +719,544 @@ where
         self.specialize_map::<T, R>(convert::identity, f, r)
     }
 
+    /// Specialize on the parameter and the return type of the closure, which
+    /// may decline the parameter at runtime.
+    ///
+    /// Returning [`ControlFlow::Continue`] with the parameter falls through to
+    /// the arms added before this one, and finally the fallback.
+    ///
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    ///
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     Specializer::new(ty, |_| "other".to_owned())
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .specialize_declinable(|int: i32| {
+    ///             if int < 0 {
+    ///                 return ControlFlow::Continue(int);
+    ///             }
+    ///
+    ///             ControlFlow::Break(format!("positive int {int}"))
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "positive int 3");
+    /// assert_eq!(describe(-3), "int -3");
+    /// assert_eq!(describe(3u8), "other");
+    /// ```
+    #[inline]
+    pub fn specialize_declinable<P, R>(
+        self,
+        f: impl FnOnce(P) -> ControlFlow<R, P>,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let Specializer(ty, fallback, phantom_data, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return match f(param) {
+                    ControlFlow::Break(ret) => {
+                        crate::trace::taken::<T, P>();
+                        crate::cast_identity::<R, U>(ret).unwrap()
+                    }
+                    ControlFlow::Continue(param) => {
+                        crate::trace::missed::<T, P>(armed);
+                        fallback(crate::cast_identity::<P, T>(param).unwrap())
+                    }
+                };
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        Specializer(ty, f, phantom_data, matched, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, only
+    /// when `predicate` holds for the parameter.
+    ///
+    /// Otherwise, the parameter falls through to the arms added before this
+    /// one, and finally the fallback.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     Specializer::new(ty, |_| "other".to_owned())
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .specialize_filter(
+    ///             |int: &i32| *int > 0,
+    ///             |int| -> String { format!("positive int {int}") },
+    ///         )
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "positive int 3");
+    /// assert_eq!(describe(-3), "int -3");
+    /// assert_eq!(describe(3u8), "other");
+    /// ```
+    #[inline]
+    pub fn specialize_filter<P, R>(
+        self,
+        predicate: impl FnOnce(&P) -> bool,
+        f: impl FnOnce(P) -> R,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.specialize_declinable(|p: P| {
+            if predicate(&p) {
+                return ControlFlow::Break(f(p));
+            }
+
+            ControlFlow::Continue(p)
+        })
+    }
+
+    /// Call `f` with a reference to the parameter when dispatch reaches this
+    /// point, before the arms added earlier (and the fallback) are consulted.
+    ///
+    /// Added last, `f` sees every parameter; added directly after
+    /// [`new()`](Specializer::new), it only sees parameters that no arm
+    /// specializes on, right before the fallback runs.
+    ///
+    /// ```rust
+    /// use std::{any, cell::RefCell};
+    ///
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T, log: &RefCell<Vec<&str>>) -> String {
+    ///     Specializer::new(ty, |_| "unknown".to_owned())
+    ///         .inspect_param(|_| log.borrow_mut().push(any::type_name::<T>()))
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .run()
+    /// }
+    ///
+    /// let log = RefCell::new(Vec::new());
+    ///
+    /// assert_eq!(describe(3, &log), "int 3");
+    /// assert_eq!(describe(true, &log), "unknown");
+    /// assert_eq!(*log.borrow(), ["bool"]);
+    /// ```
+    #[inline]
+    pub fn inspect_param(
+        self,
+        f: impl FnOnce(&T),
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        let Specializer(ty, next, phantom_data, matched, armed) = self;
+        let f = |t: T| -> U {
+            f(&t);
+            next(t)
+        };
+
+        Specializer(ty, f, phantom_data, matched, armed)
+    }
+
+    /// Add the arms added by `f` only if `condition` is true.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T, verbose: bool) -> String {
+    ///     Specializer::new(ty, |_| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| int.to_string())
+    ///         .when(verbose, |spec| {
+    ///             spec.specialize_param(|int: i32| format!("int {int}"))
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3, false), "3");
+    /// assert_eq!(describe(3, true), "int 3");
+    /// assert_eq!(describe((), true), "unknown");
+    /// ```
+    #[inline]
+    pub fn when<G>(
+        self,
+        condition: bool,
+        f: impl FnOnce(Self) -> Specializer<T, U, G>,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U>
+    where
+        G: FnOnce(T) -> U,
+    {
+        let (ty, next, matched, armed) = if condition {
+            let Specializer(ty, next, PhantomData, matched, armed) = f(self);
+
+            (ty, Ok(next), matched, armed)
+        } else {
+            let Specializer(ty, next, PhantomData, matched, armed) = self;
+
+            (ty, Err(next), matched, armed)
+        };
+        let f = |t: T| -> U {
+            match next {
+                Ok(next) => next(t),
+                Err(next) => next(t),
+            }
+        };
+
+        Specializer(ty, f, PhantomData, matched, armed)
+    }
+
+    /// Map the return value of the whole chain (the arms added so far and the
+    /// fallback) with `f`.
+    ///
+    /// Arms added afterwards specialize on the new return type `V`.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     Specializer::new(ty, |_| "unknown")
+    ///         .specialize_param(|_: i32| "int")
+    ///         .map_return(|name| format!("<{name}>"))
+    ///         .specialize_param(|string: String| string)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), "<int>");
+    /// assert_eq!(describe(()), "<unknown>");
+    /// assert_eq!(describe("Hello".to_owned()), "Hello");
+    /// ```
+    #[inline]
+    pub fn map_return<V>(
+        self,
+        f: impl FnOnce(U) -> V,
+    ) -> Specializer<T, V, impl FnOnce(T) -> V>
+    where
+        V: 'static,
+    {
+        let Specializer(ty, next, PhantomData, matched, armed) = self;
+        let f = |t: T| -> V { f(next(t)) };
+
+        Specializer(ty, f, PhantomData, matched, armed)
+    }
+
+    /// Convert into a [`SpecializerBorrowedParam`], keeping the arms added so
+    /// far, so that the next arms can take borrowed parameters.
+    #[inline(always)]
+    pub fn into_borrowed_param(self) -> SpecializerBorrowedParam<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+    {
+        let Specializer(ty, f, PhantomData, matched, armed) = self;
+
+        SpecializerBorrowedParam::from_parts(ty, f, matched, armed)
+    }
+
+    /// Convert into a [`SpecializerBorrowedReturn`], keeping the arms added so
+    /// far, so that the next arms can return borrowed types.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// static BYTES: [u8; 3] = [1, 2, 3];
+    ///
+    /// fn lookup<T, U>(ty: T) -> Option<&'static U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static,
+    /// {
+    ///     Specializer::new(ty, |_| None)
+    ///         .specialize_param(|(): ()| None)
+    ///         .into_borrowed_return()
+    ///         .specialize(|index: usize| -> Option<&u8> { BYTES.get(index) })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(lookup::<usize, u8>(1), Some(&2));
+    /// assert_eq!(lookup::<usize, u8>(3), None);
+    /// assert_eq!(lookup::<usize, u16>(1), None);
+    /// ```
+    #[inline(always)]
+    pub fn into_borrowed_return(self) -> SpecializerBorrowedReturn<T, U, F>
+    where
+        U: CastIdentityBorrowed<U>,
+    {
+        SpecializerBorrowedReturn::from_parts(self.0, self.1, self.3, self.4)
+    }
+
+    /// Convert into a [`SpecializerBorrowed`], keeping the arms added so far.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> SpecializerBorrowed<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+        U: CastIdentityBorrowed<U>,
+    {
+        SpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0)
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// Arms that may decline the parameter at runtime
+    /// ([`specialize_declinable()`](Specializer::specialize_declinable) and
+    /// [`specialize_filter()`](Specializer::specialize_filter)) count as
+    /// specializing on it, so the fallback still runs when they all decline.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> Result<String, T> {
+    ///     Specializer::new(ty, |_| unreachable!())
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .specialize_param(|string: String| string)
+    ///         .try_run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), Ok("int 3".to_owned()));
+    /// assert_eq!(describe("Hello".to_owned()), Ok("Hello".to_owned()));
+    /// assert_eq!(describe(3u8), Err(3));
+    /// ```
+    #[inline]
+    pub fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run())
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn total<T: 'static>(ty: T) -> u64 {
+    ///     Specializer::new(ty, |_| 0u8)
+    ///         .specialize_param(|(): ()| 1)
+    ///         .run_into()
+    /// }
+    ///
+    /// assert_eq!(total(()), 1);
+    /// assert_eq!(total(3), 0);
+    /// ```
+    #[inline]
+    pub fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](Specializer::new) is dropped.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn doubled<T: 'static + Copy>(ty: T) -> Option<T> {
+    ///     let double = Specializer::new(ty, |_| None)
+    ///         .specialize(|int: i32| -> Option<i32> { Some(int * 2) })
+    ///         .into_fn();
+    ///
+    ///     Some(ty).and_then(double)
+    /// }
+    ///
+    /// assert_eq!(doubled(3), Some(6));
+    /// assert_eq!(doubled('a'), None);
+    /// ```
+    #[inline(always)]
+    pub fn into_fn(self) -> impl FnOnce(T) -> U {
+        self.1
+    }
+
+    /// Return a reference to the parameter.
+    #[inline(always)]
+    pub const fn param(&self) -> &T {
+        &self.0
+    }
+
+    /// Return a mutable reference to the parameter.
+    #[inline(always)]
+    pub const fn param_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Return the parameter and the composed closure without running it.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T, dry_run: bool) -> Result<String, T> {
+    ///     let specializer = Specializer::new(ty, |_| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| format!("int {int}"));
+    ///
+    ///     if dry_run {
+    ///         return Err(specializer.into_parts().0);
+    ///     }
+    ///
+    ///     Ok(specializer.run())
+    /// }
+    ///
+    /// assert_eq!(describe(3, false), Ok("int 3".to_owned()));
+    /// assert_eq!(describe(3, true), Err(3));
+    /// ```
+    #[inline(always)]
+    pub fn into_parts(self) -> (T, F) {
+        (self.0, self.1)
+    }
+}
+
+impl<T, U, F> Specializer<T, U, F>
+where
+    F: Fn(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Start building a chain of arms with a fallback function, before
+    /// having the parameter.
+    ///
+    /// See [`SpecializerChain::build()`].
+    #[inline(always)]
+    pub const fn builder(f: F) -> SpecializerChain<T, U, F> {
+        SpecializerChain::new(f)
+    }
+}
+
+#[expect(clippy::type_complexity)]
+impl<A, B, U, F> Specializer<(A, B), U, F>
+where
+    F: FnOnce((A, B)) -> U,
+    A: 'static,
+    B: 'static,
+    U: 'static,
+{
+    /// Specialize on the type of the first element of the parameter pair,
+    /// passing the second through unchanged.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn repeat<T: 'static>(ty: T, count: usize) -> String {
+    ///     Specializer::new((ty, count), |_| String::new())
+    ///         .specialize_first(|string: String, count| string.repeat(count))
+    ///         .specialize_first(|ch: char, n| ch.to_string().repeat(n))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(repeat("ab".to_owned(), 2), "abab");
+    /// assert_eq!(repeat('a', 3), "aaa");
+    /// assert_eq!(repeat(3, 2), "");
+    /// ```
+    #[inline]
+    pub fn specialize_first<P>(
+        self,
+        f: impl FnOnce(P, B) -> U,
+    ) -> Specializer<(A, B), U, impl FnOnce((A, B)) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(|(a, b): (P, B)| f(a, b))
+    }
+
+    /// Specialize on the type of the second element of the parameter pair,
+    /// passing the first through unchanged.
+    ///
+    /// See [`specialize_first()`](Specializer::specialize_first).
+    #[inline]
+    pub fn specialize_second<P>(
+        self,
+        f: impl FnOnce(A, P) -> U,
+    ) -> Specializer<(A, B), U, impl FnOnce((A, B)) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(|(a, b): (A, P)| f(a, b))
+    }
+}
+
+#[expect(clippy::type_complexity)]
+impl<A, B, C, U, F> Specializer<(A, B, C), U, F>
+where
+    F: FnOnce((A, B, C)) -> U,
+    A: 'static,
+    B: 'static,
+    C: 'static,
+    U: 'static,
+{
+    /// Specialize on the type of the first element of the parameter triple,
+    /// passing the others through unchanged.
+    ///
+    /// See [`specialize_first()`](Specializer::specialize_first).
+    #[inline]
+    pub fn specialize_first<P>(
+        self,
+        f: impl FnOnce(P, B, C) -> U,
+    ) -> Specializer<(A, B, C), U, impl FnOnce((A, B, C)) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(|(a, b, c): (P, B, C)| f(a, b, c))
+    }
+
+    /// Specialize on the type of the second element of the parameter triple,
+    /// passing the others through unchanged.
+    ///
+    /// See [`specialize_first()`](Specializer::specialize_first).
+    #[inline]
+    pub fn specialize_second<P>(
+        self,
+        f: impl FnOnce(A, P, C) -> U,
+    ) -> Specializer<(A, B, C), U, impl FnOnce((A, B, C)) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(|(a, b, c): (A, P, C)| f(a, b, c))
+    }
+
+    /// Specialize on the type of the third element of the parameter triple,
+    /// passing the others through unchanged.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn label<T>(name: &'static str, width: usize, value: T) -> String
+    /// where
+    ///     T: 'static + std::fmt::Display,
+    /// {
+    ///     Specializer::new((name, width, value), |(name, width, value)| {
+    ///         format!("{name}: {value:width$}")
+    ///     })
+    ///         .specialize_third(|name, width, value: f64| {
+    ///             format!("{name}: {value:width$.2}")
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(label("int", 3, 7), "int:   7");
+    /// assert_eq!(label("float", 6, 0.5), "float:   0.50");
+    /// ```
+    #[inline]
+    pub fn specialize_third<P>(
+        self,
+        f: impl FnOnce(A, B, P) -> U,
+    ) -> Specializer<(A, B, C), U, impl FnOnce((A, B, C)) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_param(|(a, b, c): (A, B, P)| f(a, b, c))
+    }
 }
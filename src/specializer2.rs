@@ -0,0 +1,196 @@
+use core::marker::PhantomData;
+
+/// Double-dispatched specialized behavior runner (Owned pair -> Owned)
+///
+/// Like [`Specializer`](crate::Specializer), but over two independent
+/// parameters, with arms specialized on the type of either or both of them.
+///
+/// ```rust
+/// use specializer::Specializer2;
+///
+/// fn combine<A, B>(a: A, b: B) -> String
+/// where
+///     A: 'static + ToString,
+///     B: 'static + ToString,
+/// {
+///     let fallback = |a: A, b: B| a.to_string() + &b.to_string();
+///
+///     Specializer2::new(a, b, fallback)
+///         .specialize_left(|a: i32, b| (a * 10).to_string() + &b.to_string())
+///         .specialize_right(|a, b: i32| a.to_string() + &(b * 10).to_string())
+///         .specialize_both(|a: i32, b: i32| (a + b).to_string())
+///         .run()
+/// }
+///
+/// assert_eq!(combine(1, 2), "3");
+/// assert_eq!(combine(1, 'b'), "10b");
+/// assert_eq!(combine('a', 2), "a20");
+/// assert_eq!(combine('a', 'b'), "ab");
+/// ```
+#[derive(Debug)]
+pub struct Specializer2<T1, T2, U, F>(
+    T1,
+    T2,
+    F,
+    PhantomData<fn(T1, T2) -> U>,
+    bool,
+);
+
+impl<T1, T2, U, F> Specializer2<T1, T2, U, F>
+where
+    F: FnOnce(T1, T2) -> U,
+    T1: 'static,
+    T2: 'static,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(left: T1, right: T2, f: F) -> Self {
+        Self(left, right, f, PhantomData, false)
+    }
+
+    /// Specialize on the left parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::Specializer2;
+    ///
+    /// fn specialized<A, B>(a: A, b: B) -> Option<i32>
+    /// where
+    ///     A: 'static,
+    ///     B: 'static,
+    /// {
+    ///     Specializer2::new(a, b, |_, _| None)
+    ///         .specialize_left(|a: i32, _| Some(a * 2))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(3, "right"), Some(6));
+    /// assert_eq!(specialized(3u8, "right"), None);
+    /// ```
+    #[inline]
+    pub fn specialize_left<P, R>(
+        self,
+        f: impl FnOnce(P, T2) -> R,
+    ) -> Specializer2<T1, T2, U, impl FnOnce(T1, T2) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let Specializer2(left, right, fallback, PhantomData, armed) = self;
+        let f = move |t1: T1, t2: T2| -> U {
+            if crate::would_specialize::<T1, U, P, R>() {
+                crate::trace::taken::<(T1, T2), P>();
+                let param = crate::cast_identity::<T1, P>(t1).unwrap();
+
+                return crate::cast_identity::<R, U>(f(param, t2)).unwrap();
+            }
+
+            crate::trace::missed::<(T1, T2), P>(armed);
+
+            fallback(t1, t2)
+        };
+
+        Specializer2(left, right, f, PhantomData, true)
+    }
+
+    /// Specialize on the right parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::Specializer2;
+    ///
+    /// fn specialized<A, B>(a: A, b: B) -> Option<i32>
+    /// where
+    ///     A: 'static,
+    ///     B: 'static,
+    /// {
+    ///     Specializer2::new(a, b, |_, _| None)
+    ///         .specialize_right(|_, b: i32| Some(b * 2))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized("left", 3), Some(6));
+    /// assert_eq!(specialized("left", 3u8), None);
+    /// ```
+    #[inline]
+    pub fn specialize_right<P, R>(
+        self,
+        f: impl FnOnce(T1, P) -> R,
+    ) -> Specializer2<T1, T2, U, impl FnOnce(T1, T2) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let Specializer2(left, right, fallback, PhantomData, armed) = self;
+        let f = move |t1: T1, t2: T2| -> U {
+            if crate::would_specialize::<T2, U, P, R>() {
+                crate::trace::taken::<(T1, T2), P>();
+                let param = crate::cast_identity::<T2, P>(t2).unwrap();
+
+                return crate::cast_identity::<R, U>(f(t1, param)).unwrap();
+            }
+
+            crate::trace::missed::<(T1, T2), P>(armed);
+
+            fallback(t1, t2)
+        };
+
+        Specializer2(left, right, f, PhantomData, true)
+    }
+
+    /// Specialize on both parameters and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::Specializer2;
+    ///
+    /// fn specialized<A, B>(a: A, b: B) -> Option<i32>
+    /// where
+    ///     A: 'static,
+    ///     B: 'static,
+    /// {
+    ///     Specializer2::new(a, b, |_, _| None)
+    ///         .specialize_both(|a: i32, b: u8| Some(a * i32::from(b)))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(3, 2u8), Some(6));
+    /// assert_eq!(specialized(3, 2), None);
+    /// assert_eq!(specialized(3u8, 2u8), None);
+    /// ```
+    #[inline]
+    pub fn specialize_both<P1, P2, R>(
+        self,
+        f: impl FnOnce(P1, P2) -> R,
+    ) -> Specializer2<T1, T2, U, impl FnOnce(T1, T2) -> U>
+    where
+        P1: 'static,
+        P2: 'static,
+        R: 'static,
+    {
+        let Specializer2(left, right, fallback, PhantomData, armed) = self;
+        let f = move |t1: T1, t2: T2| -> U {
+            if crate::would_specialize::<T1, U, P1, R>()
+                && crate::api::type_eq::<T2, P2>()
+            {
+                crate::trace::taken::<(T1, T2), (P1, P2)>();
+                let p1 = crate::cast_identity::<T1, P1>(t1).unwrap();
+                let p2 = crate::cast_identity::<T2, P2>(t2).unwrap();
+
+                return crate::cast_identity::<R, U>(f(p1, p2)).unwrap();
+            }
+
+            crate::trace::missed::<(T1, T2), (P1, P2)>(armed);
+
+            fallback(t1, t2)
+        };
+
+        Specializer2(left, right, f, PhantomData, true)
+    }
+
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) -> U {
+        crate::trace::fallback::<(T1, T2)>(self.4);
+
+        (self.2)(self.0, self.1)
+    }
+}
@@ -4,7 +4,13 @@ use crate::CastIdentityBorrowed;
 
 /// Specialized behavior runner (Borrowed -> Borrowed)
 #[derive(Debug)]
-pub struct SpecializerBorrowed<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct SpecializerBorrowed<T, U, F>(
+    T,
+    F,
+    PhantomData<fn(T) -> U>,
+    bool,
+    bool,
+);
 
 impl<T, U, F> SpecializerBorrowed<T, U, F>
 where
@@ -15,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -47,21 +66,28 @@ where
         T: CastIdentityBorrowed<P>,
         R: CastIdentityBorrowed<U>,
     {
-        let SpecializerBorrowed(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
+        let SpecializerBorrowed(ty, fallback, PhantomData, matched, armed) =
+            self;
+        let f = move |t: T| -> U {
             if <R as CastIdentityBorrowed<U>>::is_same()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
 
-                return crate::cast_identity_borrowed::<R, U>(f(param))
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(f(param));
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowed(ty, f, phantom_data)
+        let matched = matched
+            || <R as CastIdentityBorrowed<U>>::is_same()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        SpecializerBorrowed(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -123,24 +149,30 @@ where
         R: CastIdentityBorrowed<U>,
         U: CastIdentityBorrowed<R>,
     {
-        let SpecializerBorrowed(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
+        let SpecializerBorrowed(ty, fallback, PhantomData, matched, armed) =
+            self;
+        let f = move |t: T| -> U {
             if <U as CastIdentityBorrowed<R>>::is_same()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
-                let param =
-                    crate::cast_identity_borrowed::<P, T>(p(param)).unwrap();
-                let ret =
-                    crate::cast_identity_borrowed::<U, R>(f(param)).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
+                let param = crate::api::cast_matched::<P, T>(p(param));
+                let ret = crate::api::cast_matched::<U, R>(f(param));
 
-                return crate::cast_identity_borrowed::<R, U>(r(ret)).unwrap();
+                return crate::api::cast_matched::<R, U>(r(ret));
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowed(ty, f, phantom_data)
+        let matched = matched
+            || <U as CastIdentityBorrowed<R>>::is_same()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        SpecializerBorrowed(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -329,6 +361,53 @@ where
     /// Run the specializer.
     #[inline]
     pub fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0)
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn name<T: 'static>(ty: &T) -> Result<&'static str, &T> {
+    ///     Specializer::by_ref(ty, |_| unreachable!())
+    ///         .specialize_param(|(): &()| "unit")
+    ///         .into_borrowed()
+    ///         .specialize_param(|_: &String| "String")
+    ///         .try_run()
+    /// }
+    ///
+    /// assert_eq!(name(&()), Ok("unit"));
+    /// assert_eq!(name(&String::new()), Ok("String"));
+    /// assert_eq!(name(&3), Err(&3));
+    /// ```
+    #[inline]
+    pub fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run())
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](SpecializerBorrowed::new) is dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl FnOnce(T) -> U {
+        self.1
+    }
 }
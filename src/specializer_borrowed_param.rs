@@ -1,10 +1,16 @@
-use core::{any::TypeId, convert, marker::PhantomData};
+use core::{convert, marker::PhantomData};
 
-use crate::CastIdentityBorrowed;
+use crate::{CastIdentityBorrowed, SpecializerBorrowed};
 
 /// Specialized behavior runner (Borrowed -> Owned)
 #[derive(Debug)]
-pub struct SpecializerBorrowedParam<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct SpecializerBorrowedParam<T, U, F>(
+    T,
+    F,
+    PhantomData<fn(T) -> U>,
+    bool,
+    bool,
+);
 
 impl<T, U, F> SpecializerBorrowedParam<T, U, F>
 where
@@ -15,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -47,20 +66,27 @@ where
         T: CastIdentityBorrowed<P>,
         R: 'static,
     {
-        let SpecializerBorrowedParam(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
-            if TypeId::of::<U>() == TypeId::of::<R>()
+        let Self(ty, fallback, PhantomData, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::api::type_eq::<U, R>()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
 
                 return crate::cast_identity::<R, U>(f(param)).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowedParam(ty, f, phantom_data)
+        let matched = matched
+            || crate::api::type_eq::<U, R>()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        SpecializerBorrowedParam(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -107,23 +133,29 @@ where
         P: CastIdentityBorrowed<T>,
         R: 'static,
     {
-        let SpecializerBorrowedParam(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
-            if TypeId::of::<U>() == TypeId::of::<R>()
+        let Self(ty, fallback, PhantomData, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::api::type_eq::<U, R>()
                 && <T as CastIdentityBorrowed<P>>::is_same()
             {
-                let param = crate::cast_identity_borrowed::<T, P>(t).unwrap();
-                let param =
-                    crate::cast_identity_borrowed::<P, T>(p(param)).unwrap();
+                crate::trace::taken::<T, P>();
+                let param = crate::api::cast_matched::<T, P>(t);
+                let param = crate::api::cast_matched::<P, T>(p(param));
                 let ret = crate::cast_identity::<U, R>(f(param)).unwrap();
 
                 return crate::cast_identity::<R, U>(r(ret)).unwrap();
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowedParam(ty, f, phantom_data)
+        let matched = matched
+            || crate::api::type_eq::<U, R>()
+                && <T as CastIdentityBorrowed<P>>::is_same();
+
+        SpecializerBorrowedParam(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -272,9 +304,66 @@ where
         self.specialize_map::<T, R>(convert::identity, f, r)
     }
 
+    /// Convert into a [`SpecializerBorrowed`], keeping the arms added so far,
+    /// so that the next arms can return borrowed types.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> SpecializerBorrowed<T, U, F>
+    where
+        U: CastIdentityBorrowed<U>,
+    {
+        SpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0)
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn len<T: 'static>(ty: &T) -> Result<usize, &T> {
+    ///     Specializer::by_ref(ty, |_| unreachable!())
+    ///         .specialize_param(|string: &String| string.len())
+    ///         .specialize_param(|bytes: &Vec<u8>| bytes.len())
+    ///         .try_run()
+    /// }
+    ///
+    /// assert_eq!(len(&"Hello".to_owned()), Ok(5));
+    /// assert_eq!(len(&vec![1u8, 2]), Ok(2));
+    /// assert_eq!(len(&3), Err(&3));
+    /// ```
+    #[inline]
+    pub fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run())
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](SpecializerBorrowedParam::new) is
+    /// dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl FnOnce(T) -> U {
+        self.1
+    }
 }
@@ -1,10 +1,16 @@
-use core::{any::TypeId, convert, marker::PhantomData};
+use core::{convert, marker::PhantomData};
 
-use crate::CastIdentityBorrowed;
+use crate::{CastIdentityBorrowed, SpecializerBorrowed};
 
 /// Specialized behavior runner (Owned -> Borrowed)
 #[derive(Debug)]
-pub struct SpecializerBorrowedReturn<T, U, F>(T, F, PhantomData<fn(T) -> U>);
+pub struct SpecializerBorrowedReturn<T, U, F>(
+    T,
+    F,
+    PhantomData<fn(T) -> U>,
+    bool,
+    bool,
+);
 
 impl<T, U, F> SpecializerBorrowedReturn<T, U, F>
 where
@@ -15,7 +21,20 @@ where
     /// Create a new specializer with a fallback function.
     #[inline(always)]
     pub const fn new(params: T, f: F) -> Self {
-        Self(params, f, PhantomData)
+        Self(params, f, PhantomData, false, false)
+    }
+
+    /// Create a new specializer with a fallback function that specializes on
+    /// the parameter type if `matched` is true, and that is wrapped by arms if
+    /// `armed` is true.
+    #[inline(always)]
+    pub(crate) const fn from_parts(
+        params: T,
+        f: F,
+        matched: bool,
+        armed: bool,
+    ) -> Self {
+        Self(params, f, PhantomData, matched, armed)
     }
 
     /// Specialize on the parameter and the return type of the closure.
@@ -47,21 +66,33 @@ where
         P: 'static,
         R: CastIdentityBorrowed<U>,
     {
-        let SpecializerBorrowedReturn(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
+        let SpecializerBorrowedReturn(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = move |t: T| -> U {
             if <R as CastIdentityBorrowed<U>>::is_same()
-                && TypeId::of::<T>() == TypeId::of::<P>()
+                && crate::api::type_eq::<T, P>()
             {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
 
-                return crate::cast_identity_borrowed::<R, U>(f(param))
-                    .unwrap();
+                return crate::api::cast_matched::<R, U>(f(param));
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowedReturn(ty, f, phantom_data)
+        let matched = matched
+            || <R as CastIdentityBorrowed<U>>::is_same()
+                && crate::api::type_eq::<T, P>();
+
+        SpecializerBorrowedReturn(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter and the return type of the closure, mapping
@@ -105,23 +136,35 @@ where
         R: CastIdentityBorrowed<U>,
         U: CastIdentityBorrowed<R>,
     {
-        let SpecializerBorrowedReturn(ty, fallback, phantom_data) = self;
-        let f = |t: T| -> U {
+        let SpecializerBorrowedReturn(
+            ty,
+            fallback,
+            PhantomData,
+            matched,
+            armed,
+        ) = self;
+        let f = move |t: T| -> U {
             if <U as CastIdentityBorrowed<R>>::is_same()
-                && TypeId::of::<T>() == TypeId::of::<P>()
+                && crate::api::type_eq::<T, P>()
             {
+                crate::trace::taken::<T, P>();
                 let param = crate::cast_identity::<T, P>(t).unwrap();
                 let param = crate::cast_identity::<P, T>(p(param)).unwrap();
-                let ret =
-                    crate::cast_identity_borrowed::<U, R>(f(param)).unwrap();
+                let ret = crate::api::cast_matched::<U, R>(f(param));
 
-                return crate::cast_identity_borrowed::<R, U>(r(ret)).unwrap();
+                return crate::api::cast_matched::<R, U>(r(ret));
             }
 
+            crate::trace::missed::<T, P>(armed);
+
             fallback(t)
         };
 
-        SpecializerBorrowedReturn(ty, f, phantom_data)
+        let matched = matched
+            || <U as CastIdentityBorrowed<R>>::is_same()
+                && crate::api::type_eq::<T, P>();
+
+        SpecializerBorrowedReturn(ty, f, PhantomData, matched, true)
     }
 
     /// Specialize on the parameter of the closure.
@@ -269,9 +312,69 @@ where
         self.specialize_map::<T, R>(convert::identity, f, r)
     }
 
+    /// Convert into a [`SpecializerBorrowed`], keeping the arms added so far,
+    /// so that the next arms can take borrowed parameters.
+    #[inline(always)]
+    pub fn into_borrowed(self) -> SpecializerBorrowed<T, U, F>
+    where
+        T: CastIdentityBorrowed<T>,
+    {
+        SpecializerBorrowed::from_parts(self.0, self.1, self.3, self.4)
+    }
+
     /// Run the specializer.
     #[inline]
     pub fn run(self) -> U {
+        crate::trace::fallback::<T>(self.4);
+
         (self.1)(self.0)
     }
+
+    /// Run the specializer, handing back the parameter instead of calling the
+    /// fallback if no arm specializes on its type.
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// static BYTES: [u8; 3] = [1, 2, 3];
+    ///
+    /// fn lookup<T: 'static>(ty: T) -> Result<Option<&'static u8>, T> {
+    ///     Specializer::new(ty, |_| unreachable!())
+    ///         .specialize_param(|(): ()| None)
+    ///         .into_borrowed_return()
+    ///         .specialize_param(|index: usize| BYTES.get(index))
+    ///         .try_run()
+    /// }
+    ///
+    /// assert_eq!(lookup(()), Ok(None));
+    /// assert_eq!(lookup(1usize), Ok(Some(&2)));
+    /// assert_eq!(lookup(1u8), Err(1));
+    /// ```
+    #[inline]
+    pub fn try_run(self) -> Result<U, T> {
+        if !self.3 {
+            return Err(self.0);
+        }
+
+        Ok(self.run())
+    }
+
+    /// Run the specializer, converting the result into `V`.
+    #[inline]
+    pub fn run_into<V>(self) -> V
+    where
+        U: Into<V>,
+    {
+        self.run().into()
+    }
+
+    /// Return the composed closure, so it can be called with another
+    /// parameter or passed where a plain closure is expected.
+    ///
+    /// The parameter passed to [`new()`](SpecializerBorrowedReturn::new) is
+    /// dropped.
+    #[inline(always)]
+    pub fn into_fn(self) -> impl FnOnce(T) -> U {
+        self.1
+    }
 }
@@ -0,0 +1,374 @@
+use core::{convert, marker::PhantomData};
+
+/// Reusable specialized behavior runner (Owned -> Owned)
+///
+/// Like [`Specializer`](crate::Specializer), but built from [`Fn`] arms
+/// without the parameter, so that it can be built once and run any number of
+/// times with [`run_with()`](SpecializerChain::run_with).
+#[derive(Clone, Copy, Debug)]
+pub struct SpecializerChain<T, U, F>(F, PhantomData<fn(T) -> U>, bool, bool);
+
+impl<T, U, F> SpecializerChain<T, U, F>
+where
+    F: Fn(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer chain with a fallback function.
+    #[inline(always)]
+    pub const fn new(f: F) -> Self {
+        Self(f, PhantomData, false, false)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T> + From<u8>,
+    /// {
+    ///     let chain = SpecializerChain::new(From::from)
+    ///         .specialize(|int: i32| -> i32 { int * 2 })
+    ///         .specialize_param(|int: u8| U::from(int * 3));
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<i32, i32>(vec![1, 3]), [2, 6]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl Fn(P) -> R,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerChain(fallback, PhantomData, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return crate::cast_identity::<R, U>(f(param)).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        SpecializerChain(f, PhantomData, matched, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::convert;
+    ///
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let chain = SpecializerChain::new(From::from).specialize_map(
+    ///         |int: u8| int * 3,
+    ///         From::from,
+    ///         convert::identity::<U>,
+    ///     );
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl Fn(P) -> P,
+        f: impl Fn(T) -> U,
+        r: impl Fn(R) -> R,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerChain(fallback, PhantomData, matched, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+                let param = crate::cast_identity::<P, T>(p(param)).unwrap();
+                let ret = crate::cast_identity::<U, R>(f(param)).unwrap();
+
+                return crate::cast_identity::<R, U>(r(ret)).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        let matched = matched || crate::would_specialize::<T, U, P, R>();
+
+        SpecializerChain(f, PhantomData, matched, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T: 'static>(tys: Vec<T>) -> Vec<String> {
+    ///     let chain = SpecializerChain::new(|_| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| (int * 2).to_string())
+    ///         .specialize_param(|string: String| string);
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized(vec![1, 3]), ["2", "6"]);
+    /// assert_eq!(specialized(vec!["Hello".to_owned()]), ["Hello"]);
+    /// assert_eq!(specialized(vec![()]), ["unknown"]);
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl Fn(P) -> U,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T>(ints: Vec<i32>) -> Vec<T>
+    /// where
+    ///     T: 'static + Default,
+    /// {
+    ///     let fallback = |_: i32| -> T { Default::default() };
+    ///     let chain = SpecializerChain::new(fallback)
+    ///         .specialize_return(|int| -> i32 { int * 2 })
+    ///         .specialize_return(|int| -> String { int.to_string() });
+    ///
+    ///     ints.into_iter().map(|int| chain.run_with(int)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32>(vec![1, 3]), [2, 6]);
+    /// assert_eq!(specialized::<String>(vec![1, 3]), ["1", "3"]);
+    /// assert_eq!(specialized::<u8>(vec![1, 3]), [0, 0]);
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl Fn(T) -> R,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let chain = SpecializerChain::new(From::from)
+    ///         .specialize_map_param(|int: u8| int * 3, From::from);
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl Fn(P) -> P,
+        f: impl Fn(T) -> U,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, convert::identity)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let chain = SpecializerChain::new(From::from)
+    ///         .specialize_map_return(From::from, |int: i16| int * 2);
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i8, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<i8, i16>(vec![1, 3]), [2, 6]);
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl Fn(T) -> U,
+        r: impl Fn(R) -> R,
+    ) -> SpecializerChain<T, U, impl Fn(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(convert::identity, f, r)
+    }
+
+    /// Map the parameter of the whole chain (the arms added so far and the
+    /// fallback) from `V` with `f`.
+    ///
+    /// Arms added afterwards specialize on the new parameter type `V`.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerChain;
+    ///
+    /// fn lengths<T: 'static>(tys: Vec<Box<T>>) -> Vec<usize> {
+    ///     let chain = SpecializerChain::new(|_| 0)
+    ///         .specialize_param(|string: String| string.len())
+    ///         .map_param(|boxed: Box<T>| *boxed)
+    ///         .specialize_param(|int: Box<u32>| *int as usize);
+    ///
+    ///     tys.into_iter().map(|ty| chain.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(lengths(vec![Box::new("ab".to_owned())]), [2]);
+    /// assert_eq!(lengths(vec![Box::new(3u32)]), [3]);
+    /// assert_eq!(lengths(vec![Box::new(())]), [0]);
+    /// ```
+    #[inline]
+    pub fn map_param<V>(
+        self,
+        f: impl Fn(V) -> T,
+    ) -> SpecializerChain<V, U, impl Fn(V) -> U>
+    where
+        V: 'static,
+    {
+        let SpecializerChain(next, PhantomData, matched, armed) = self;
+        let f = move |v: V| -> U { next(f(v)) };
+
+        SpecializerChain(f, PhantomData, matched, armed)
+    }
+
+    /// Map the return value of the whole chain (the arms added so far and the
+    /// fallback) with `f`.
+    ///
+    /// Arms added afterwards specialize on the new return type `V`.
+    ///
+    /// See [`Specializer::map_return()`](crate::Specializer::map_return).
+    #[inline]
+    pub fn map_return<V>(
+        self,
+        f: impl Fn(U) -> V,
+    ) -> SpecializerChain<T, V, impl Fn(T) -> V>
+    where
+        V: 'static,
+    {
+        let SpecializerChain(next, PhantomData, matched, armed) = self;
+        let f = move |t: T| -> V { f(next(t)) };
+
+        SpecializerChain(f, PhantomData, matched, armed)
+    }
+
+    /// Run the specializer chain on `params`.
+    #[inline]
+    pub fn run_with(&self, params: T) -> U {
+        crate::trace::fallback::<T>(self.3);
+
+        (self.0)(params)
+    }
+
+    /// Finish building the chain, returning it as a function.
+    ///
+    /// ```rust
+    /// use std::fmt::Debug;
+    ///
+    /// use specializer::Specializer;
+    ///
+    /// struct Describe<F> {
+    ///     describe: F,
+    /// }
+    ///
+    /// fn prepare<T: 'static + Debug>() -> Describe<impl Fn(T) -> String> {
+    ///     let describe = Specializer::builder(|ty: T| format!("{ty:?}"))
+    ///         .specialize_param(|string: String| string)
+    ///         .build();
+    ///
+    ///     Describe { describe }
+    /// }
+    ///
+    /// let strings = prepare::<String>();
+    /// let ints = prepare::<i32>();
+    ///
+    /// assert_eq!((strings.describe)("Hello".to_owned()), "Hello");
+    /// assert_eq!((ints.describe)(3), "3");
+    /// assert_eq!((ints.describe)(4), "4");
+    /// ```
+    ///
+    /// Closures returned by the `specialize*()` methods aren't [`Clone`], so to
+    /// fork a partially built chain, build it and use it by reference as the
+    /// fallback of each fork:
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn forks<T: 'static>(ty: T, loud: bool) -> String {
+    ///     let base = Specializer::builder(|_: T| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| int.to_string())
+    ///         .build();
+    ///
+    ///     if loud {
+    ///         return Specializer::new(ty, &base)
+    ///             .specialize_param(|string: String| string.to_uppercase())
+    ///             .run();
+    ///     }
+    ///
+    ///     Specializer::new(ty, &base)
+    ///         .specialize_param(|string: String| string)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(forks("Hello".to_owned(), true), "HELLO");
+    /// assert_eq!(forks("Hello".to_owned(), false), "Hello");
+    /// assert_eq!(forks(3, true), "3");
+    /// ```
+    #[inline(always)]
+    pub fn build(self) -> F {
+        self.0
+    }
+}
@@ -0,0 +1,277 @@
+use core::{convert, marker::PhantomData};
+
+/// Reusable specialized behavior runner with stateful arms (Owned -> Owned)
+///
+/// Like [`Specializer`](crate::Specializer), but built from [`FnMut`] arms
+/// without the parameter, so that arms can update their captured state each
+/// time it's run with [`run_with()`](SpecializerMut::run_with).
+#[derive(Debug)]
+pub struct SpecializerMut<T, U, F>(F, PhantomData<fn(T) -> U>, bool);
+
+impl<T, U, F> SpecializerMut<T, U, F>
+where
+    F: FnMut(T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn count<T: 'static>(tys: Vec<T>) -> (usize, usize) {
+    ///     let (mut ints, mut others) = (0, 0);
+    ///     let mut specializer = SpecializerMut::new(|_| others += 1)
+    ///         .specialize_param(|_: i32| ints += 1);
+    ///
+    ///     for ty in tys {
+    ///         specializer.run_with(ty);
+    ///     }
+    ///
+    ///     drop(specializer);
+    ///     (ints, others)
+    /// }
+    ///
+    /// assert_eq!(count(vec![1, 2, 3]), (3, 0));
+    /// assert_eq!(count(vec!["a", "b"]), (0, 2));
+    /// ```
+    #[inline(always)]
+    pub const fn new(f: F) -> Self {
+        Self(f, PhantomData, false)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T> + From<u8>,
+    /// {
+    ///     let mut specializer = SpecializerMut::new(From::from)
+    ///         .specialize(|int: i32| -> i32 { int * 2 })
+    ///         .specialize_param(|int: u8| U::from(int * 3));
+    ///
+    ///     tys.into_iter().map(|ty| specializer.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<i32, i32>(vec![1, 3]), [2, 6]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        mut f: impl FnMut(P) -> R,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerMut(mut fallback, PhantomData, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return crate::cast_identity::<R, U>(f(param)).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        SpecializerMut(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::convert;
+    ///
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let specializer = SpecializerMut::new(From::from);
+    ///     let mut specializer = specializer.specialize_map(
+    ///         |int: u8| int * 3,
+    ///         From::from,
+    ///         convert::identity::<U>,
+    ///     );
+    ///
+    ///     tys.into_iter().map(|ty| specializer.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        mut p: impl FnMut(P) -> P,
+        mut f: impl FnMut(T) -> U,
+        mut r: impl FnMut(R) -> R,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerMut(mut fallback, PhantomData, armed) = self;
+        let f = move |t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+                let param = crate::cast_identity::<P, T>(p(param)).unwrap();
+                let ret = crate::cast_identity::<U, R>(f(param)).unwrap();
+
+                return crate::cast_identity::<R, U>(r(ret)).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(t)
+        };
+
+        SpecializerMut(f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T: 'static>(tys: Vec<T>) -> Vec<String> {
+    ///     let mut specializer = SpecializerMut::new(|_| "unknown".to_owned())
+    ///         .specialize_param(|int: i32| (int * 2).to_string())
+    ///         .specialize_param(|string: String| string);
+    ///
+    ///     tys.into_iter().map(|ty| specializer.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized(vec![1, 3]), ["2", "6"]);
+    /// assert_eq!(specialized(vec!["Hello".to_owned()]), ["Hello"]);
+    /// assert_eq!(specialized(vec![()]), ["unknown"]);
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnMut(P) -> U,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T>(ints: Vec<i32>) -> Vec<T>
+    /// where
+    ///     T: 'static + Default,
+    /// {
+    ///     let fallback = |_: i32| -> T { Default::default() };
+    ///     let mut specializer = SpecializerMut::new(fallback)
+    ///         .specialize_return(|int| -> i32 { int * 2 })
+    ///         .specialize_return(|int| -> String { int.to_string() });
+    ///
+    ///     ints.into_iter().map(|int| specializer.run_with(int)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32>(vec![1, 3]), [2, 6]);
+    /// assert_eq!(specialized::<String>(vec![1, 3]), ["1", "3"]);
+    /// assert_eq!(specialized::<u8>(vec![1, 3]), [0, 0]);
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnMut(T) -> R,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let mut specializer = SpecializerMut::new(From::from)
+    ///         .specialize_map_param(|int: u8| int * 3, From::from);
+    ///
+    ///     tys.into_iter().map(|ty| specializer.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i16, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<u8, i32>(vec![1, 3]), [3, 9]);
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnMut(P) -> P,
+        f: impl FnMut(T) -> U,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, convert::identity)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerMut;
+    ///
+    /// fn specialized<T, U>(tys: Vec<T>) -> Vec<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     let mut specializer = SpecializerMut::new(From::from)
+    ///         .specialize_map_return(From::from, |int: i16| int * 2);
+    ///
+    ///     tys.into_iter().map(|ty| specializer.run_with(ty)).collect()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i8, i32>(vec![1, 3]), [1, 3]);
+    /// assert_eq!(specialized::<i8, i16>(vec![1, 3]), [2, 6]);
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnMut(T) -> U,
+        r: impl FnMut(R) -> R,
+    ) -> SpecializerMut<T, U, impl FnMut(T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(convert::identity, f, r)
+    }
+
+    /// Run the specializer on `params`.
+    #[inline]
+    pub fn run_with(&mut self, params: T) -> U {
+        crate::trace::fallback::<T>(self.2);
+
+        (self.0)(params)
+    }
+}
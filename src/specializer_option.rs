@@ -0,0 +1,375 @@
+use core::convert;
+
+use crate::Specializer;
+
+/// Specialized behavior runner without a fallback (Owned -> Owned)
+///
+/// Like [`Specializer`], but [`run()`](SpecializerOption::run) returns `None`
+/// when no arm matches (or [`try_run()`](SpecializerOption::try_run) hands
+/// back the parameter), instead of calling a fallback function.
+#[derive(Debug)]
+pub struct SpecializerOption<T, U, F>(Specializer<T, Result<U, T>, F>);
+
+impl<T, U> SpecializerOption<T, U, fn(T) -> Result<U, T>>
+where
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer without a fallback function.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T: 'static>(ty: T) -> Option<String> {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_param(|int: i32| (int * 2).to_string())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(3).as_deref(), Some("6"));
+    /// assert_eq!(specialized(()), None);
+    /// ```
+    #[inline(always)]
+    pub const fn new(params: T) -> Self {
+        Self(Specializer::new(params, Err))
+    }
+}
+
+impl<T, U, F> SpecializerOption<T, U, F>
+where
+    F: FnOnce(T) -> Result<U, T>,
+    T: 'static,
+    U: 'static,
+{
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Option<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static,
+    /// {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize(|int: i32| -> i32 { int * 2 })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32, i32>(3), Some(6));
+    /// assert_eq!(specialized::<i32, i64>(3), None);
+    /// assert_eq!(specialized::<u8, i32>(3), None);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(P) -> R,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        SpecializerOption(
+            self.0.specialize(|p: P| -> Result<R, T> { Ok(f(p)) }),
+        )
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::convert;
+    ///
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Option<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_map(
+    ///             |int: u8| int * 3,
+    ///             From::from,
+    ///             convert::identity::<i32>,
+    ///         )
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8, i32>(3), Some(9));
+    /// assert_eq!(specialized::<u8, i64>(3), None);
+    /// assert_eq!(specialized::<i16, i32>(3), None);
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        SpecializerOption(self.0.specialize_map::<P, Result<R, T>>(
+            p,
+            |t| Ok(f(t)),
+            |ret| ret.map(r),
+        ))
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T: 'static>(ty: T) -> Option<String> {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_param(|int: i32| (int * 2).to_string())
+    ///         .specialize_param(|string: String| string)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized(3).as_deref(), Some("6"));
+    /// assert_eq!(specialized("Hello".to_string()).as_deref(), Some("Hello"));
+    /// assert_eq!(specialized(()), None);
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(P) -> U,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T: 'static>(int: i32) -> Option<T> {
+    ///     SpecializerOption::new(int)
+    ///         .specialize_return(|int| -> i32 { int * 2 })
+    ///         .specialize_return(|int| -> String { int.to_string() })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i32>(3), Some(6));
+    /// assert_eq!(specialized::<String>(3).as_deref(), Some("3"));
+    /// assert_eq!(specialized::<u8>(3), None);
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnOnce(T) -> R,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Option<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_map_param(|int: u8| int * 3, From::from)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8, i32>(3), Some(9));
+    /// assert_eq!(specialized::<i16, i32>(3), None);
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> U,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, convert::identity)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Option<U>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_map_return(From::from, |int: i16| int * 2)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<i8, i16>(3), Some(6));
+    /// assert_eq!(specialized::<i8, i32>(3), None);
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnOnce(T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(convert::identity, f, r)
+    }
+
+    /// Call `f` with a reference to the parameter when no arm takes it, right
+    /// before it is handed back (or passed to the fallback added with
+    /// [`or()`](SpecializerOption::or)).
+    ///
+    /// Unlike [`Specializer::inspect_param()`], it may be added anywhere in the
+    /// chain.
+    ///
+    /// ```rust
+    /// use std::{any, cell::RefCell};
+    ///
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T, log: &RefCell<Vec<&str>>) -> String {
+    ///     let name = any::type_name::<T>();
+    ///
+    ///     Specializer::for_value(ty)
+    ///         .specialize_param(|int: i32| int.to_string())
+    ///         .inspect_fallback(|_| log.borrow_mut().push(name))
+    ///         .specialize_param(|string: String| string)
+    ///         .fallback(|_| "unknown".to_owned())
+    ///         .run()
+    /// }
+    ///
+    /// let log = RefCell::new(Vec::new());
+    ///
+    /// assert_eq!(describe(3, &log), "3");
+    /// assert_eq!(describe("Hello".to_owned(), &log), "Hello");
+    /// assert_eq!(describe(true, &log), "unknown");
+    /// assert_eq!(*log.borrow(), ["bool"]);
+    /// ```
+    #[inline]
+    pub fn inspect_fallback(
+        self,
+        f: impl FnOnce(&T),
+    ) -> SpecializerOption<T, U, impl FnOnce(T) -> Result<U, T>> {
+        SpecializerOption(self.0.map_return(|ret| ret.inspect_err(f)))
+    }
+
+    /// Run the specializer, returning `None` if no arm matches.
+    ///
+    /// To get the parameter back when no arm matches, use
+    /// [`try_run()`](SpecializerOption::try_run).
+    #[inline]
+    pub fn run(self) -> Option<U> {
+        self.0.run().ok()
+    }
+
+    /// Run the specializer, handing back the unconsumed parameter if no arm
+    /// matches.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// fn describe<T: 'static + std::fmt::Debug>(ty: T) -> String {
+    ///     let ty = match SpecializerOption::new(ty)
+    ///         .specialize_param(|string: String| string)
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .try_run()
+    ///     {
+    ///         Ok(string) => return string,
+    ///         Err(ty) => ty,
+    ///     };
+    ///
+    ///     format!("{ty:?}")
+    /// }
+    ///
+    /// assert_eq!(describe("Hello".to_string()), "Hello");
+    /// assert_eq!(describe(3), "int 3");
+    /// assert_eq!(describe([1u8, 2]), "[1, 2]");
+    /// ```
+    #[inline]
+    pub fn try_run(self) -> Result<U, T> {
+        self.0.run()
+    }
+
+    /// Fall back to `next` when no arm matches, for example the closure
+    /// built by another chain of arms.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerOption;
+    ///
+    /// mod ints {
+    ///     use specializer::Specializer;
+    ///
+    ///     pub fn describe<T: 'static>() -> impl Fn(T) -> String {
+    ///         Specializer::builder(|_| "unknown".to_owned())
+    ///             .specialize_param(|int: i32| format!("int {int}"))
+    ///             .build()
+    ///     }
+    /// }
+    ///
+    /// fn describe<T: 'static>(ty: T) -> String {
+    ///     SpecializerOption::new(ty)
+    ///         .specialize_param(|string: String| format!("string {string}"))
+    ///         .or(ints::describe())
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(describe("Hello".to_owned()), "string Hello");
+    /// assert_eq!(describe(3), "int 3");
+    /// assert_eq!(describe(()), "unknown");
+    /// ```
+    #[inline]
+    pub fn or(
+        self,
+        next: impl FnOnce(T) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        self.0.map_return(|ret| ret.unwrap_or_else(next))
+    }
+
+    /// Add the fallback function last, after the arms.
+    ///
+    /// Same as [`or()`](SpecializerOption::or); see
+    /// [`Specializer::for_value()`].
+    ///
+    /// The arms added before the fallback still count as specializing on the
+    /// parameter type for [`Specializer::try_run()`].
+    ///
+    /// ```rust
+    /// use specializer::Specializer;
+    ///
+    /// fn describe<T: 'static>(ty: T) -> Result<String, T> {
+    ///     Specializer::for_value(ty)
+    ///         .specialize_param(|int: i32| format!("int {int}"))
+    ///         .fallback(|_| unreachable!())
+    ///         .try_run()
+    /// }
+    ///
+    /// assert_eq!(describe(3), Ok("int 3".to_owned()));
+    /// assert_eq!(describe(3u8), Err(3));
+    /// ```
+    #[inline(always)]
+    pub fn fallback(
+        self,
+        f: impl FnOnce(T) -> U,
+    ) -> Specializer<T, U, impl FnOnce(T) -> U> {
+        self.or(f)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::SpecializerBorrowedParam;
+
+/// In-place specialized mutation runner (Mutably borrowed -> Nothing)
+///
+/// Like [`SpecializerBorrowedParam`] over `&mut T` returning `()`, but
+/// without a fallback: values that no arm specializes on are left as they
+/// are.
+#[derive(Debug)]
+pub struct SpecializerVisitor<'a, T, F>(
+    SpecializerBorrowedParam<&'a mut T, (), F>,
+)
+where
+    T: 'static;
+
+impl<'a, T> SpecializerVisitor<'a, T, fn(&'a mut T)>
+where
+    T: 'static,
+{
+    /// Create a new specializer that leaves `value` unchanged unless an arm
+    /// specializes on its type.
+    #[inline(always)]
+    pub const fn new(value: &'a mut T) -> Self {
+        Self(SpecializerBorrowedParam::new(value, drop))
+    }
+}
+
+impl<'a, T, F> SpecializerVisitor<'a, T, F>
+where
+    F: FnOnce(&'a mut T),
+    T: 'static,
+{
+    /// Specialize on the type of the value the closure mutates.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerVisitor;
+    ///
+    /// fn normalize<T: 'static>(value: &mut T) {
+    ///     SpecializerVisitor::new(value)
+    ///         .specialize(|string: &mut String| string.make_ascii_lowercase())
+    ///         .specialize(|float: &mut f32| *float = float.clamp(0.0, 1.0))
+    ///         .run()
+    /// }
+    ///
+    /// let mut string = "Hello".to_owned();
+    /// let mut float = 1.5f32;
+    /// let mut int = 3;
+    ///
+    /// normalize(&mut string);
+    /// normalize(&mut float);
+    /// normalize(&mut int);
+    /// assert_eq!(string, "hello");
+    /// assert_eq!(float, 1.0);
+    /// assert_eq!(int, 3);
+    /// ```
+    #[inline]
+    pub fn specialize<P>(
+        self,
+        f: impl FnOnce(&mut P),
+    ) -> SpecializerVisitor<'a, T, impl FnOnce(&'a mut T)>
+    where
+        P: 'static,
+    {
+        SpecializerVisitor(self.0.specialize_param(|p: &'a mut P| f(p)))
+    }
+
+    /// Run the specializer.
+    #[inline]
+    pub fn run(self) {
+        self.0.run()
+    }
+}
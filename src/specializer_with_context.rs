@@ -0,0 +1,301 @@
+use core::{convert, marker::PhantomData};
+
+/// Specialized behavior runner with a shared context (Owned -> Owned)
+///
+/// Like [`Specializer`](crate::Specializer), but the fallback and every arm
+/// also receive a `&mut C` context, passed in to
+/// [`run()`](SpecializerWithContext::run), so that arms don't need to
+/// capture it.
+#[derive(Debug)]
+pub struct SpecializerWithContext<C: ?Sized, T, U, F>(
+    T,
+    F,
+    PhantomData<fn(&mut C, T) -> U>,
+    bool,
+);
+
+impl<C, T, U, F> SpecializerWithContext<C, T, U, F>
+where
+    C: ?Sized,
+    F: FnOnce(&mut C, T) -> U,
+    T: 'static,
+    U: 'static,
+{
+    /// Create a new specializer with a fallback function.
+    #[inline(always)]
+    pub const fn new(params: T, f: F) -> Self {
+        Self(params, f, PhantomData, false)
+    }
+
+    /// Specialize on the parameter and the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn specialized<T, U>(calls: &mut u32, ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerWithContext::new(ty, |_: &mut u32, ty| U::from(ty))
+    ///         .specialize(|calls: &mut u32, int: i32| -> i32 {
+    ///             *calls += 1;
+    ///             int * 2
+    ///         })
+    ///         .run(calls)
+    /// }
+    ///
+    /// let mut calls = 0;
+    ///
+    /// assert_eq!(specialized::<i16, i32>(&mut calls, 3), 3);
+    /// assert_eq!(specialized::<i32, i32>(&mut calls, 3), 6);
+    /// assert_eq!(calls, 1);
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(&mut C, P) -> R,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerWithContext(ty, fallback, PhantomData, armed) = self;
+        let f = move |context: &mut C, t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+
+                return crate::cast_identity::<R, U>(f(context, param))
+                    .unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(context, t)
+        };
+
+        SpecializerWithContext(ty, f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// both.
+    ///
+    /// ```rust
+    /// use std::convert;
+    ///
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn specialized<T, U>(calls: &mut u32, ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerWithContext::new(ty, |_: &mut u32, ty| U::from(ty))
+    ///         .specialize_map(
+    ///             |int: i16| int + 1,
+    ///             |calls, ty| {
+    ///                 *calls += 1;
+    ///                 U::from(ty)
+    ///             },
+    ///             convert::identity::<i32>,
+    ///         )
+    ///         .run(calls)
+    /// }
+    ///
+    /// let mut calls = 0;
+    ///
+    /// assert_eq!(specialized::<i16, i32>(&mut calls, 3), 4);
+    /// assert_eq!(specialized::<i8, i32>(&mut calls, 3), 3);
+    /// assert_eq!(calls, 1);
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(&mut C, T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let SpecializerWithContext(ty, fallback, PhantomData, armed) = self;
+        let f = move |context: &mut C, t: T| -> U {
+            if crate::would_specialize::<T, U, P, R>() {
+                crate::trace::taken::<T, P>();
+                let param = crate::cast_identity::<T, P>(t).unwrap();
+                let param = crate::cast_identity::<P, T>(p(param)).unwrap();
+                let ret =
+                    crate::cast_identity::<U, R>(f(context, param)).unwrap();
+
+                return crate::cast_identity::<R, U>(r(ret)).unwrap();
+            }
+
+            crate::trace::missed::<T, P>(armed);
+
+            fallback(context, t)
+        };
+
+        SpecializerWithContext(ty, f, PhantomData, true)
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use std::fmt::Write;
+    ///
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn describe<T: 'static>(out: &mut String, ty: T) {
+    ///     let fallback = |out: &mut String, _| out.push_str("unknown; ");
+    ///
+    ///     SpecializerWithContext::new(ty, fallback)
+    ///         .specialize_param(|out: &mut String, int: i32| {
+    ///             write!(out, "int {int}; ").unwrap()
+    ///         })
+    ///         .specialize_param(|out: &mut String, string: String| {
+    ///             write!(out, "string {string:?}; ").unwrap()
+    ///         })
+    ///         .run(out)
+    /// }
+    ///
+    /// let mut out = String::new();
+    ///
+    /// describe(&mut out, 3);
+    /// describe(&mut out, "Hello".to_string());
+    /// describe(&mut out, ());
+    /// assert_eq!(out, "int 3; string \"Hello\"; unknown; ");
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(&mut C, P) -> U,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn specialized<T>(calls: &mut u32, int: i32) -> T
+    /// where
+    ///     T: 'static + Default,
+    /// {
+    ///     let fallback = |_: &mut u32, _| -> T { Default::default() };
+    ///
+    ///     SpecializerWithContext::new(int, fallback)
+    ///         .specialize_return(|calls: &mut u32, int| -> String {
+    ///             *calls += 1;
+    ///             int.to_string()
+    ///         })
+    ///         .run(calls)
+    /// }
+    ///
+    /// let mut calls = 0;
+    ///
+    /// assert_eq!(specialized::<String>(&mut calls, 3), "3");
+    /// assert_eq!(specialized::<u8>(&mut calls, 3), 0);
+    /// assert_eq!(calls, 1);
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnOnce(&mut C, T) -> R,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the parameter.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn specialized<T, U>(calls: &mut u32, ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerWithContext::new(ty, |_: &mut u32, ty| U::from(ty))
+    ///         .specialize_map_param(|int: i16| int + 1, |calls, ty| {
+    ///             *calls += 1;
+    ///             U::from(ty)
+    ///         })
+    ///         .run(calls)
+    /// }
+    ///
+    /// let mut calls = 0;
+    ///
+    /// assert_eq!(specialized::<i16, i32>(&mut calls, 3), 4);
+    /// assert_eq!(specialized::<i8, i32>(&mut calls, 3), 3);
+    /// assert_eq!(calls, 1);
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(&mut C, T) -> U,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        P: 'static,
+    {
+        self.specialize_map::<P, U>(p, f, convert::identity)
+    }
+
+    /// Specialize on the parameter and the return type of the closure, mapping
+    /// the return value.
+    ///
+    /// ```rust
+    /// use specializer::SpecializerWithContext;
+    ///
+    /// fn specialized<T, U>(calls: &mut u32, ty: T) -> U
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + From<T>,
+    /// {
+    ///     SpecializerWithContext::new(ty, |_: &mut u32, ty| U::from(ty))
+    ///         .specialize_map_return(
+    ///             |calls, ty| {
+    ///                 *calls += 1;
+    ///                 U::from(ty)
+    ///             },
+    ///             |int: i16| int * 2,
+    ///         )
+    ///         .run(calls)
+    /// }
+    ///
+    /// let mut calls = 0;
+    ///
+    /// assert_eq!(specialized::<i8, i16>(&mut calls, 3), 6);
+    /// assert_eq!(specialized::<i8, i32>(&mut calls, 3), 3);
+    /// assert_eq!(calls, 1);
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnOnce(&mut C, T) -> U,
+        r: impl FnOnce(R) -> R,
+    ) -> SpecializerWithContext<C, T, U, impl FnOnce(&mut C, T) -> U>
+    where
+        R: 'static,
+    {
+        self.specialize_map::<T, R>(convert::identity, f, r)
+    }
+
+    /// Run the specializer, passing `context` to the arm that's taken.
+    #[inline]
+    pub fn run(self, context: &mut C) -> U {
+        crate::trace::fallback::<T>(self.3);
+
+        (self.1)(context, self.0)
+    }
+}
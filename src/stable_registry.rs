@@ -0,0 +1,154 @@
+use alloc::boxed::Box;
+use core::{any::Any, fmt, marker::PhantomData};
+
+use crate::{
+    StableType, StableTypeId, dyn_specializer::DynArm, small_map::SmallMap,
+};
+
+/// Registered arm, with a check that the parameter can be handed to it
+struct Arm<U> {
+    /// Return true if a parameter with the stable type identifier, and the
+    /// type of the `PhantomData` passed in, can be handed to the arm
+    accepts: fn(StableTypeId, &dyn Any) -> bool,
+    run: Box<DynArm<U>>,
+}
+
+/// Runtime registry of specialized behavior keyed by [`StableTypeId`]
+///
+/// Works like [`DynSpecializer`](crate::DynSpecializer), except that arms are
+/// looked up by an identifier that doesn't change between compilations, so a
+/// registry filled in by dynamically loaded plugins finds the same arms as
+/// the host expects.
+///
+/// Before a parameter is handed to an arm, both its stable type identifier
+/// and its [`TypeId`](core::any::TypeId) are checked against the arm's, so if
+/// the host's and plugin's views of a type disagree (or two types share a
+/// stable identifier), dispatch fails with `Err(param)` instead of running
+/// the arm.
+///
+/// ```rust
+/// use specializer::StableRegistry;
+///
+/// struct Ping(u32);
+///
+/// specializer::stable_type!(Ping => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f);
+///
+/// let mut registry = StableRegistry::new();
+///
+/// registry
+///     .register(|ping: Ping| format!("pong {}", ping.0))
+///     .register(|int: i32| (int * 2).to_string());
+///
+/// assert_eq!(registry.dispatch(Ping(7)).ok(), Some("pong 7".to_owned()));
+/// assert_eq!(registry.dispatch(3i32).ok(), Some("6".to_owned()));
+/// assert!(registry.dispatch(3u8).is_err());
+/// ```
+pub struct StableRegistry<U> {
+    arms: SmallMap<StableTypeId, Arm<U>>,
+}
+
+impl<U> StableRegistry<U> {
+    /// Create a new registry without any registered arms.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            arms: SmallMap::new(),
+        }
+    }
+
+    /// Register an arm for parameter type `P`.
+    ///
+    /// Registering a second arm for the same stable type identifier replaces
+    /// the first.
+    pub fn register<P>(
+        &mut self,
+        f: impl Fn(P) -> U + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: StableType,
+    {
+        fn accepts<P: StableType>(id: StableTypeId, ty: &dyn Any) -> bool {
+            id == P::STABLE_TYPE_ID && ty.is::<PhantomData<P>>()
+        }
+
+        let run = move |any: &mut dyn Any| -> U { f(crate::__take_param(any)) };
+
+        self.arms.insert(
+            P::STABLE_TYPE_ID,
+            Arm {
+                accepts: accepts::<P>,
+                run: Box::new(run),
+            },
+        );
+        self
+    }
+
+    /// Return true if an arm is registered under `id`.
+    pub fn contains(&self, id: StableTypeId) -> bool {
+        self.arms.contains_key(&id)
+    }
+
+    /// Return the number of registered arms.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Return true if no arms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.arms.len() == 0
+    }
+
+    /// Run the arm registered for the stable type identifier of `param`.
+    ///
+    /// Returns `Err(param)` if no arm is registered for `T`, or if the
+    /// registered arm's parameter type isn't `T`.
+    ///
+    /// ```rust
+    /// use specializer::StableRegistry;
+    ///
+    /// struct Ping(u32);
+    /// struct Pong(u32);
+    ///
+    /// // Mistakenly published with the same identifier
+    /// specializer::stable_type! {
+    ///     Ping => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f,
+    ///     Pong => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f,
+    /// }
+    ///
+    /// let mut registry = StableRegistry::new();
+    ///
+    /// registry.register(|ping: Ping| ping.0);
+    ///
+    /// assert_eq!(registry.dispatch(Ping(7)).ok(), Some(7));
+    /// assert_eq!(registry.dispatch(Pong(8)).map_err(|pong| pong.0), Err(8));
+    /// ```
+    #[inline]
+    pub fn dispatch<T>(&self, param: T) -> Result<U, T>
+    where
+        T: StableType,
+    {
+        let arm = self
+            .arms
+            .get(&T::STABLE_TYPE_ID)
+            .filter(|arm| (arm.accepts)(T::STABLE_TYPE_ID, &PhantomData::<T>));
+        let Some(arm) = crate::trace::found::<T, _>(arm) else {
+            return Err(param);
+        };
+
+        Ok((arm.run)(&mut Some(param)))
+    }
+}
+
+impl<U> Default for StableRegistry<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> fmt::Debug for StableRegistry<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StableRegistry")
+            .field("arms", &self.arms.len())
+            .finish()
+    }
+}
@@ -0,0 +1,139 @@
+/// Implement [`StableType`] for one or more types.
+///
+/// Each type is paired with a `u128` identifier (typically a UUID) that must
+/// be unique among types used as registry keys, and must never change once
+/// published.
+///
+/// ```rust
+/// use specializer::{StableType, StableTypeId};
+///
+/// struct Ping;
+/// struct Pong;
+///
+/// specializer::stable_type! {
+///     Ping => 0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f_0e41,
+///     Pong => 0x0d52_b8a7_1f63_4c09_9e2d_5a74_c816_3fb2,
+/// }
+///
+/// assert_eq!(
+///     StableTypeId::of::<Ping>(),
+///     StableTypeId::from_u128(0x6f1c_3e4a_90b2_4d7e_8a15_2c9d_7b3f_0e41),
+/// );
+/// assert_ne!(Ping::STABLE_TYPE_ID, Pong::STABLE_TYPE_ID);
+/// ```
+#[macro_export]
+macro_rules! stable_type {
+    ($($ty:ty => $id:expr),* $(,)?) => {$(
+        impl $crate::StableType for $ty {
+            const STABLE_TYPE_ID: $crate::StableTypeId =
+                $crate::StableTypeId::from_u128($id);
+        }
+    )*};
+}
+
+/// Type identifier that is stable across compilations
+///
+/// Unlike [`TypeId`](core::any::TypeId), which may differ between builds (and
+/// therefore between a host and a dynamically loaded plugin), a
+/// `StableTypeId` is chosen by the author of the type, so it can be used as a
+/// key across dynamic library boundaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StableTypeId(u128);
+
+impl StableTypeId {
+    /// Create a stable type identifier from a raw `u128` (typically a UUID).
+    #[inline(always)]
+    pub const fn from_u128(id: u128) -> Self {
+        Self(id)
+    }
+
+    /// Create a stable type identifier by hashing a fully-qualified type name.
+    ///
+    /// Uses 128-bit FNV-1a, so the result only depends on `name`.
+    ///
+    /// ```rust
+    /// use specializer::StableTypeId;
+    ///
+    /// const ID: StableTypeId = StableTypeId::from_name("my_crate::Message");
+    ///
+    /// assert_eq!(ID, StableTypeId::from_name("my_crate::Message"));
+    /// assert_ne!(ID, StableTypeId::from_name("my_crate::Reply"));
+    /// ```
+    pub const fn from_name(name: &str) -> Self {
+        const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+        const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+        let bytes = name.as_bytes();
+        let mut hash = OFFSET;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            hash ^= bytes[i] as u128;
+            hash = hash.wrapping_mul(PRIME);
+            i += 1;
+        }
+
+        Self(hash)
+    }
+
+    /// Return the stable type identifier of `T`.
+    #[inline(always)]
+    pub const fn of<T>() -> Self
+    where
+        T: StableType + ?Sized,
+    {
+        T::STABLE_TYPE_ID
+    }
+
+    /// Return the raw `u128` value of the identifier.
+    #[inline(always)]
+    pub const fn as_u128(self) -> u128 {
+        self.0
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for StableTypeId {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "StableTypeId({=u128:#x})", self.0);
+    }
+}
+
+/// Types with an identifier that is stable across compilations
+///
+/// Implement with [`stable_type!`](crate::stable_type).  Implemented for the
+/// primitive types, with identifiers hashed from their names.
+pub trait StableType: 'static {
+    /// The stable identifier of this type
+    const STABLE_TYPE_ID: StableTypeId;
+}
+
+macro_rules! primitives {
+    ($($ty:ty),*) => {$(
+        impl StableType for $ty {
+            const STABLE_TYPE_ID: StableTypeId =
+                StableTypeId::from_name(stringify!($ty));
+        }
+    )*};
+}
+
+primitives!(
+    (),
+    bool,
+    char,
+    str,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
@@ -0,0 +1,126 @@
+use core::any::{Any, TypeId};
+
+/// Build a [`StaticArm`] from a `ParamType => handler` pair.
+///
+/// The handler must not capture its environment (it is coerced to a function
+/// pointer), which allows the arm to be constructed in a `static`.
+///
+/// ```rust
+/// use specializer::StaticArm;
+///
+/// static ARMS: &[StaticArm<String>] = &[
+///     specializer::static_arm!(i32 => |int| (int * 2).to_string()),
+///     specializer::static_arm!(String => |string| string),
+/// ];
+///
+/// fn specialized<T: 'static>(ty: T) -> String {
+///     StaticArm::dispatch(ARMS, ty).unwrap_or_else(|_| "unknown".to_owned())
+/// }
+///
+/// assert_eq!(specialized(3), "6");
+/// assert_eq!(specialized("Hello world".to_string()), "Hello world");
+/// assert_eq!(specialized(()), "unknown");
+/// ```
+#[macro_export]
+macro_rules! static_arm {
+    ($param:ty => $f:expr $(,)?) => {
+        $crate::StaticArm::__new(
+            ::core::any::TypeId::of::<$param>,
+            ::core::any::type_name::<$param>,
+            |any: &mut dyn ::core::any::Any| {
+                let f: fn($param) -> _ = $f;
+
+                f($crate::__take_param::<$param>(any))
+            },
+        )
+    };
+}
+
+/// Specialized arm built entirely from function pointers
+///
+/// Since it can be constructed in a `static` (with
+/// [`static_arm!`](crate::static_arm)), lists of arms can be assembled at
+/// link time.  With the `linkme` feature enabled, dependencies can contribute
+/// arms to a distributed slice declared by the application:
+///
+/// ```rust
+/// # #[cfg(feature = "linkme")]
+/// # {
+/// use specializer::{StaticArm, linkme::distributed_slice};
+///
+/// #[distributed_slice]
+/// #[linkme(crate = specializer::linkme)]
+/// static ARMS: [StaticArm<String>];
+///
+/// // Possibly in another crate
+/// #[distributed_slice(ARMS)]
+/// #[linkme(crate = specializer::linkme)]
+/// static ARM_I32: StaticArm<String> =
+///     specializer::static_arm!(i32 => |int| (int * 2).to_string());
+///
+/// assert_eq!(StaticArm::dispatch(&ARMS, 3i32), Ok("6".to_owned()));
+/// assert_eq!(StaticArm::dispatch(&ARMS, 3u8), Err(3));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StaticArm<U> {
+    type_id: fn() -> TypeId,
+    type_name: fn() -> &'static str,
+    arm: fn(&mut dyn Any) -> U,
+}
+
+impl<U> StaticArm<U> {
+    #[doc(hidden)]
+    pub const fn __new(
+        type_id: fn() -> TypeId,
+        type_name: fn() -> &'static str,
+        arm: fn(&mut dyn Any) -> U,
+    ) -> Self {
+        Self {
+            type_id,
+            type_name,
+            arm,
+        }
+    }
+
+    /// Return the [`TypeId`] of the parameter type this arm specializes on.
+    pub fn param_type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// Return the name of the parameter type this arm specializes on.
+    pub fn param_type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// Run the first arm in `arms` that specializes on the type of `param`.
+    ///
+    /// Returns `Err(param)` if no arm matches.
+    #[inline]
+    pub fn dispatch<T>(arms: &[Self], param: T) -> Result<U, T>
+    where
+        T: 'static,
+    {
+        let type_id = TypeId::of::<T>();
+
+        let arm = arms.iter().find(|arm| arm.param_type_id() == type_id);
+
+        match crate::trace::found::<T, _>(arm) {
+            Some(arm) => Ok((arm.arm)(&mut Some(param))),
+            None => Err(param),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn arm(&self) -> fn(&mut dyn Any) -> U {
+        self.arm
+    }
+}
+
+impl<U> Clone for StaticArm<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for StaticArm<U> {}
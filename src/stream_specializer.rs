@@ -0,0 +1,222 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::SpecializerMut;
+
+/// Specialized stream item mapper
+///
+/// A [`Stream`] that maps each item of the wrapped stream with a
+/// [`SpecializerMut`] built from arms specialized on the item type, with a
+/// fallback mapping for every other type.
+///
+/// Since the arm is picked by the `Item` type alone, the type checks fold away
+/// in optimized builds, leaving a plain call to the selected closure per item.
+///
+/// It only implements [`Stream`] if the wrapped stream and the closures are
+/// [`Unpin`], since the crate doesn't use `unsafe` for pin projection.  Pin a
+/// `!Unpin` stream first (for example, with [`pin!()`](core::pin::pin)) to
+/// wrap it.
+///
+/// ```rust
+/// use std::{
+///     pin::Pin,
+///     sync::Arc,
+///     task::{Context, Poll},
+/// };
+///
+/// use futures_core::Stream;
+/// use pasts::Executor;
+/// use specializer::StreamSpecializer;
+///
+/// struct Iter<I>(I);
+///
+/// impl<I: Iterator + Unpin> Stream for Iter<I> {
+///     type Item = I::Item;
+///
+///     fn poll_next(
+///         mut self: Pin<&mut Self>,
+///         _cx: &mut Context<'_>,
+///     ) -> Poll<Option<I::Item>> {
+///         Poll::Ready(self.0.next())
+///     }
+/// }
+///
+/// fn lengths<S>(stream: S) -> impl Stream<Item = usize> + Unpin
+/// where
+///     S: Stream + Unpin,
+///     S::Item: 'static + ToString,
+/// {
+///     StreamSpecializer::new(stream, |item| item.to_string().len())
+///         .specialize_param(|string: Arc<str>| string.len())
+/// }
+///
+/// async fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+///     let mut items = Vec::new();
+///
+///     while let Some(item) = std::future::poll_fn(|cx| {
+///         Pin::new(&mut stream).poll_next(cx)
+///     })
+///     .await
+///     {
+///         items.push(item);
+///     }
+///
+///     items
+/// }
+///
+/// Executor::default().block_on(async {
+///     let strings = Iter(["a", "bc"].into_iter().map(Arc::<str>::from));
+///     let ints = Iter([1, 22, 333].into_iter());
+///
+///     assert_eq!(collect(lengths(strings)).await, [1, 2]);
+///     assert_eq!(collect(lengths(ints)).await, [1, 2, 3]);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct StreamSpecializer<S, U, F>(S, SpecializerMut<S::Item, U, F>)
+where
+    S: Stream;
+
+impl<S, U, F> StreamSpecializer<S, U, F>
+where
+    F: FnMut(S::Item) -> U,
+    S: Stream,
+    S::Item: 'static,
+    U: 'static,
+{
+    /// Create a new stream specializer with a fallback mapping.
+    #[inline(always)]
+    pub const fn new(stream: S, f: F) -> Self {
+        Self(stream, SpecializerMut::new(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize()`].
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnMut(P) -> R,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping both.
+    ///
+    /// See [`SpecializerMut::specialize_map()`].
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnMut(P) -> P,
+        f: impl FnMut(S::Item) -> U,
+        r: impl FnMut(R) -> R,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize_map(p, f, r))
+    }
+
+    /// Specialize on the item type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize_param()`].
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnMut(P) -> U,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        P: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize_param(f))
+    }
+
+    /// Specialize on the return type of the closure.
+    ///
+    /// See [`SpecializerMut::specialize_return()`].
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnMut(S::Item) -> R,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        R: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize_return(f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping the item.
+    ///
+    /// See [`SpecializerMut::specialize_map_param()`].
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnMut(P) -> P,
+        f: impl FnMut(S::Item) -> U,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        P: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize_map_param(p, f))
+    }
+
+    /// Specialize on the item type and the return type of the closure,
+    /// mapping the return value.
+    ///
+    /// See [`SpecializerMut::specialize_map_return()`].
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnMut(S::Item) -> U,
+        r: impl FnMut(R) -> R,
+    ) -> StreamSpecializer<S, U, impl FnMut(S::Item) -> U>
+    where
+        R: 'static,
+    {
+        StreamSpecializer(self.0, self.1.specialize_map_return(f, r))
+    }
+
+    /// Return the wrapped stream.
+    #[inline(always)]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S, U, F> Stream for StreamSpecializer<S, U, F>
+where
+    F: FnMut(S::Item) -> U + Unpin,
+    S: Stream + Unpin,
+    S::Item: 'static,
+    U: 'static,
+{
+    type Item = U;
+
+    #[inline]
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<U>> {
+        let this = self.get_mut();
+
+        Pin::new(&mut this.0)
+            .poll_next(cx)
+            .map(|item| item.map(|item| this.1.run_with(item)))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
@@ -0,0 +1,115 @@
+/// Parameter type name recorded for type-erased dispatch when it is unknown
+#[cfg(feature = "alloc")]
+pub(crate) const UNKNOWN: &str = "<unknown type>";
+
+/// Record a dispatch on parameter type `T`, and whether an arm was `found`.
+///
+/// See [`found_named()`].
+#[inline(always)]
+pub(crate) fn found<T, A>(arm: Option<A>) -> Option<A>
+where
+    T: ?Sized,
+{
+    found_named(core::any::type_name::<T>(), arm)
+}
+
+/// Record a dispatch on the parameter type named `param`, and whether an arm
+/// was `found`.
+///
+/// Type-erased dispatch passes the name of the concrete type if it's known
+/// (from the registered arm or reflection), otherwise [`UNKNOWN`].  Besides
+/// [`ran()`], records the dispatch for `SpecializeProbe` with the `test-util`
+/// feature.
+#[inline(always)]
+pub(crate) fn found_named<A>(param: &'static str, arm: Option<A>) -> Option<A> {
+    ran(param, arm.is_some());
+    crate::probe::dispatched(param, arm.is_some());
+
+    arm
+}
+
+/// Record that the arm for parameter type `P` ran on a parameter of type `T`.
+///
+/// Besides [`ran()`], records the arm as taken for `SpecializeProbe` with the
+/// `test-util` feature.
+#[inline(always)]
+pub(crate) fn taken<T, P>()
+where
+    T: ?Sized,
+    P: ?Sized,
+{
+    ran(core::any::type_name::<T>(), true);
+    crate::probe::taken::<P>();
+}
+
+/// Record that the arm for parameter type `P` passed on a parameter of type
+/// `T`, to the arms added before it if `armed`, otherwise to the fallback.
+///
+/// Besides [`fallback()`], records the arm as missed for `SpecializeProbe`
+/// with the `test-util` feature.
+#[inline(always)]
+pub(crate) fn missed<T, P>(armed: bool)
+where
+    T: ?Sized,
+    P: ?Sized,
+{
+    fallback::<T>(armed);
+    crate::probe::missed::<P>();
+}
+
+/// Record the result of a group of arms on a parameter of type `T`, which is
+/// passed on to the arms added before them if `armed`, otherwise to the
+/// fallback.
+#[inline(always)]
+pub(crate) fn dispatched<T, U>(ret: Result<U, T>, armed: bool) -> Result<U, T> {
+    match ret {
+        Ok(_) => ran(core::any::type_name::<T>(), true),
+        Err(_) => fallback::<T>(armed),
+    }
+
+    ret
+}
+
+/// Record that the fallback runs on a parameter of type `T`, unless `armed`,
+/// in which case the first arm added records it when passing to the fallback.
+#[inline(always)]
+pub(crate) fn fallback<T>(armed: bool)
+where
+    T: ?Sized,
+{
+    if !armed {
+        ran(core::any::type_name::<T>(), false);
+    }
+}
+
+/// Record that a dispatch on the parameter type named `param` ran an arm (or
+/// the fallback).
+///
+/// Emits a `trace`-level record with the `log` feature, a `trace`-level event
+/// with the `tracing` feature (target `specializer`), and a `defmt` trace
+/// message with the `defmt` feature on bare-metal targets.
+#[inline(always)]
+pub(crate) fn ran(param: &'static str, arm: bool) {
+    #[cfg(any(
+        feature = "log",
+        feature = "tracing",
+        all(feature = "defmt", target_os = "none"),
+    ))]
+    {
+        let ran = if arm { "arm" } else { "fallback" };
+
+        #[cfg(feature = "log")]
+        log::trace!(target: "specializer", "dispatch {param}: {ran}");
+        #[cfg(feature = "tracing")]
+        tracing::trace!(target: "specializer", param, ran, "dispatch");
+        #[cfg(all(feature = "defmt", target_os = "none"))]
+        defmt::trace!("dispatch {=str}: {=str}", param, ran);
+    }
+
+    #[cfg(not(any(
+        feature = "log",
+        feature = "tracing",
+        all(feature = "defmt", target_os = "none"),
+    )))]
+    let _ = (param, arm);
+}
@@ -0,0 +1,239 @@
+use crate::Specializer;
+
+/// Fallible specialized behavior runner (Owned -> Owned)
+///
+/// Like [`Specializer`], but the fallback and every arm return
+/// `Result<U, E>`, so that `?` works inside of them, and
+/// [`run()`](TrySpecializer::run) returns the result of the arm that's taken.
+#[derive(Debug)]
+pub struct TrySpecializer<T, U, E, F>(Specializer<T, Result<U, E>, F>);
+
+impl<T, U, E, F> TrySpecializer<T, U, E, F>
+where
+    F: FnOnce(T) -> Result<U, E>,
+    T: 'static,
+    U: 'static,
+    E: 'static,
+{
+    /// Create a new specializer with a fallible fallback function.
+    #[inline(always)]
+    pub const fn new(params: T, f: F) -> Self {
+        Self(Specializer::new(params, f))
+    }
+
+    /// Specialize on the parameter and the success type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn halve<T: 'static>(ty: T) -> Result<T, String> {
+    ///     TrySpecializer::new(ty, |_| Err("unsupported".to_owned()))
+    ///         .specialize(|int: i32| -> Result<i32, String> {
+    ///             if int % 2 != 0 {
+    ///                 return Err(format!("{int} is odd"));
+    ///             }
+    ///
+    ///             Ok(int / 2)
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(halve(6), Ok(3));
+    /// assert_eq!(halve(3), Err("3 is odd".to_owned()));
+    /// assert_eq!(halve(6u8), Err("unsupported".to_owned()));
+    /// ```
+    #[inline]
+    pub fn specialize<P, R>(
+        self,
+        f: impl FnOnce(P) -> Result<R, E>,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        TrySpecializer(self.0.specialize::<P, Result<R, E>>(f))
+    }
+
+    /// Specialize on the parameter and the success type of the closure,
+    /// mapping both.
+    ///
+    /// ```rust
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Result<U, ()>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + TryFrom<T>,
+    /// {
+    ///     let convert = |ty| U::try_from(ty).map_err(drop);
+    ///
+    ///     TrySpecializer::new(ty, convert)
+    ///         .specialize_map(|int: u8| int * 3, convert, |int: i8| int - 1)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8, i8>(3), Ok(8));
+    /// assert_eq!(specialized::<u8, i8>(50), Err(()));
+    /// assert_eq!(specialized::<u8, i16>(3), Ok(3));
+    /// ```
+    #[inline]
+    pub fn specialize_map<P, R>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> Result<U, E>,
+        r: impl FnOnce(R) -> R,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        TrySpecializer(
+            self.0
+                .specialize_map::<P, Result<R, E>>(p, f, |ret| ret.map(r)),
+        )
+    }
+
+    /// Specialize on the parameter of the closure.
+    ///
+    /// ```rust
+    /// use std::num::ParseIntError;
+    ///
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn parse<T: 'static>(ty: T) -> Result<u32, ParseIntError> {
+    ///     TrySpecializer::new(ty, |_| Ok(0))
+    ///         .specialize_param(|string: String| string.parse())
+    ///         .specialize_param(|string: &'static str| {
+    ///             let int: u32 = string.parse()?;
+    ///
+    ///             Ok(int * 2)
+    ///         })
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(parse("3".to_owned()), Ok(3));
+    /// assert_eq!(parse("3"), Ok(6));
+    /// assert!(parse("three").is_err());
+    /// assert_eq!(parse(()), Ok(0));
+    /// ```
+    #[inline]
+    pub fn specialize_param<P>(
+        self,
+        f: impl FnOnce(P) -> Result<U, E>,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        P: 'static,
+    {
+        self.specialize::<P, U>(f)
+    }
+
+    /// Specialize on the success type of the closure.
+    ///
+    /// ```rust
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn specialized<T>(int: i32) -> Result<T, String>
+    /// where
+    ///     T: 'static,
+    /// {
+    ///     TrySpecializer::new(int, |_| Err("unsupported".to_owned()))
+    ///         .specialize_return(|int| -> Result<u8, String> {
+    ///             u8::try_from(int).map_err(|e| e.to_string())
+    ///         })
+    ///         .specialize_return(|int| Ok(int.to_string()))
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8>(3), Ok(3));
+    /// assert!(specialized::<u8>(-3).is_err());
+    /// assert_eq!(specialized::<String>(3).as_deref(), Ok("3"));
+    /// assert_eq!(specialized::<i64>(3), Err("unsupported".to_owned()));
+    /// ```
+    #[inline]
+    pub fn specialize_return<R>(
+        self,
+        f: impl FnOnce(T) -> Result<R, E>,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        R: 'static,
+    {
+        self.specialize::<T, R>(f)
+    }
+
+    /// Specialize on the parameter and the success type of the closure,
+    /// mapping the parameter.
+    ///
+    /// ```rust
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Result<U, ()>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + TryFrom<T>,
+    /// {
+    ///     let convert = |ty| U::try_from(ty).map_err(drop);
+    ///
+    ///     TrySpecializer::new(ty, convert)
+    ///         .specialize_map_param(|int: u8| int * 3, convert)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8, i8>(3), Ok(9));
+    /// assert_eq!(specialized::<u8, i8>(50), Err(()));
+    /// assert_eq!(specialized::<u16, i8>(3), Ok(3));
+    /// ```
+    #[inline]
+    pub fn specialize_map_param<P>(
+        self,
+        p: impl FnOnce(P) -> P,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        P: 'static,
+    {
+        TrySpecializer(self.0.specialize_map_param::<P>(p, f))
+    }
+
+    /// Specialize on the parameter and the success type of the closure,
+    /// mapping the success value.
+    ///
+    /// ```rust
+    /// use specializer::TrySpecializer;
+    ///
+    /// fn specialized<T, U>(ty: T) -> Result<U, ()>
+    /// where
+    ///     T: 'static,
+    ///     U: 'static + TryFrom<T>,
+    /// {
+    ///     let convert = |ty| U::try_from(ty).map_err(drop);
+    ///
+    ///     TrySpecializer::new(ty, convert)
+    ///         .specialize_map_return(convert, |int: i8| int - 1)
+    ///         .run()
+    /// }
+    ///
+    /// assert_eq!(specialized::<u8, i8>(3), Ok(2));
+    /// assert_eq!(specialized::<u8, i8>(200), Err(()));
+    /// assert_eq!(specialized::<u8, i16>(3), Ok(3));
+    /// ```
+    #[inline]
+    pub fn specialize_map_return<R>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+        r: impl FnOnce(R) -> R,
+    ) -> TrySpecializer<T, U, E, impl FnOnce(T) -> Result<U, E>>
+    where
+        R: 'static,
+    {
+        TrySpecializer(
+            self.0
+                .specialize_map_return::<Result<R, E>>(f, |ret| ret.map(r)),
+        )
+    }
+
+    /// Run the specializer, returning the result of the arm that's taken.
+    #[inline]
+    pub fn run(self) -> Result<U, E> {
+        self.0.run()
+    }
+}
@@ -0,0 +1,75 @@
+/// Group of types that all convert into `B`, for arms covering every type in
+/// the group with one closure
+///
+/// Implemented for tuples of up to 12 `'static` types that implement
+/// [`Into<B>`].  See
+/// [`Specializer::specialize_all()`](crate::Specializer::specialize_all).
+///
+/// ```rust
+/// use specializer::TypeGroup;
+///
+/// type Small = (u8, u16, i8, i16);
+///
+/// assert!(<Small as TypeGroup<i32>>::contains::<u16>());
+/// assert!(!<Small as TypeGroup<i32>>::contains::<u32>());
+/// assert_eq!(<Small as TypeGroup<i32>>::convert(3u16), Ok(3i32));
+/// assert_eq!(<Small as TypeGroup<i32>>::convert(3u32), Err(3u32));
+/// ```
+pub trait TypeGroup<B> {
+    /// Return true if `T` is one of the types in the group.
+    fn contains<T>() -> bool
+    where
+        T: 'static;
+
+    /// Convert `ty` into `B` if `T` is one of the types in the group,
+    /// otherwise hand it back.
+    fn convert<T>(ty: T) -> Result<B, T>
+    where
+        T: 'static;
+}
+
+macro_rules! type_group {
+    ($($a:ident),*) => {
+        impl<B, $($a),*> TypeGroup<B> for ($($a,)*)
+        where
+            $($a: 'static + Into<B>),*
+        {
+            #[inline(always)]
+            fn contains<T>() -> bool
+            where
+                T: 'static,
+            {
+                false $(|| crate::api::type_eq::<T, $a>())*
+            }
+
+            #[inline(always)]
+            fn convert<T>(ty: T) -> Result<B, T>
+            where
+                T: 'static,
+            {
+                $(
+                    if crate::api::type_eq::<T, $a>() {
+                        return Ok(crate::cast_identity::<T, $a>(ty)
+                            .unwrap()
+                            .into());
+                    }
+                )*
+
+                Err(ty)
+            }
+        }
+    };
+}
+
+type_group!(P1);
+type_group!(P1, P2);
+type_group!(P1, P2, P3);
+type_group!(P1, P2, P3, P4);
+type_group!(P1, P2, P3, P4, P5);
+type_group!(P1, P2, P3, P4, P5, P6);
+type_group!(P1, P2, P3, P4, P5, P6, P7);
+type_group!(P1, P2, P3, P4, P5, P6, P7, P8);
+type_group!(P1, P2, P3, P4, P5, P6, P7, P8, P9);
+type_group!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+type_group!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+type_group!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
@@ -0,0 +1,184 @@
+use alloc::{string::String, vec::Vec};
+
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Convert `value` to a [`JsValue`], taking fast paths for types with a
+/// direct JavaScript representation.
+///
+///  - `JsValue` and [`Uint8Array`] are passed through
+///  - `String` and `&'static str` become JavaScript strings
+///  - `f64`, and the smaller numeric types that convert to `f64` losslessly,
+///    become JavaScript numbers
+///  - `bool` becomes a JavaScript boolean
+///  - `Vec<u8>` and `&'static [u8]` are copied into a new [`Uint8Array`]
+///
+/// Any other type is converted with `fallback`.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::JsValue;
+///
+/// fn to_js<T: 'static + std::fmt::Debug>(value: T) -> JsValue {
+///     specializer::into_js_value_fast(value, |value| {
+///         JsValue::from_str(&format!("{value:?}"))
+///     })
+/// }
+///
+/// assert_eq!(to_js("Hello".to_owned()).as_string().as_deref(), Some("Hello"));
+/// assert_eq!(to_js(1.5f64).as_f64(), Some(1.5));
+/// assert_eq!(to_js(3u8).as_f64(), Some(3.0));
+/// assert_eq!(to_js(()).as_string().as_deref(), Some("()"));
+/// ```
+pub fn into_js_value_fast<T>(
+    value: T,
+    fallback: impl FnOnce(T) -> JsValue,
+) -> JsValue
+where
+    T: 'static,
+{
+    macro_rules! number {
+        ($value:ident; $($ty:ty),*) => {$(
+            if crate::api::type_eq::<T, $ty>() {
+                let number = crate::cast_identity::<T, $ty>($value).unwrap();
+
+                return JsValue::from_f64(number.into());
+            }
+        )*};
+    }
+
+    if crate::api::type_eq::<T, JsValue>() {
+        return crate::cast_identity(value).unwrap();
+    }
+
+    if crate::api::type_eq::<T, Uint8Array>() {
+        let array = crate::cast_identity::<T, Uint8Array>(value).unwrap();
+
+        return array.into();
+    }
+
+    if let Some(string) = crate::cast_identity_ref::<T, String>(&value) {
+        return JsValue::from_str(string);
+    }
+
+    if let Some(string) = crate::cast_identity_ref::<T, &str>(&value) {
+        return JsValue::from_str(string);
+    }
+
+    number!(value; f64, f32, i32, u32, i16, u16, i8, u8);
+
+    if let Some(&boolean) = crate::cast_identity_ref::<T, bool>(&value) {
+        return JsValue::from_bool(boolean);
+    }
+
+    if let Some(bytes) = crate::cast_identity_ref::<T, Vec<u8>>(&value) {
+        return Uint8Array::from(bytes.as_slice()).into();
+    }
+
+    if let Some(bytes) = crate::cast_identity_ref::<T, &[u8]>(&value) {
+        return Uint8Array::from(*bytes).into();
+    }
+
+    fallback(value)
+}
+
+/// Convert a [`JsValue`] to a `T`, taking fast paths for types with a direct
+/// JavaScript representation.
+///
+/// Supports the same types as [`into_js_value_fast()`] (except for
+/// `&'static str` and `&'static [u8]`).  Returns `Err(value)` if `T` isn't
+/// supported, or if `value` has a different JavaScript type.  Numbers are
+/// only converted to the smaller numeric types if they are in range and
+/// represented exactly (so `1.5` isn't a `u8`, but `NaN` is an `f32`).
+///
+/// ```rust,no_run
+/// use wasm_bindgen::JsValue;
+///
+/// let value = JsValue::from_str("Hello");
+///
+/// assert_eq!(
+///     specializer::from_js_value_fast::<String>(value.clone()).ok(),
+///     Some("Hello".to_owned()),
+/// );
+/// assert!(specializer::from_js_value_fast::<f64>(value).is_err());
+///
+/// let value = JsValue::from_f64(300.0);
+///
+/// assert_eq!(
+///     specializer::from_js_value_fast::<u16>(value.clone()).ok(),
+///     Some(300),
+/// );
+/// assert!(specializer::from_js_value_fast::<u8>(value).is_err());
+/// ```
+pub fn from_js_value_fast<T>(value: JsValue) -> Result<T, JsValue>
+where
+    T: 'static,
+{
+    fn convert<T, U>(
+        value: JsValue,
+        f: impl FnOnce(&JsValue) -> Option<U>,
+    ) -> Result<T, JsValue>
+    where
+        T: 'static,
+        U: 'static,
+    {
+        match f(&value) {
+            Some(converted) => Ok(crate::cast_identity(converted).unwrap()),
+            None => Err(value),
+        }
+    }
+
+    macro_rules! number {
+        ($value:ident; $($ty:ty),*) => {$(
+            if crate::api::type_eq::<T, $ty>() {
+                return convert::<T, _>($value, |value| {
+                    let number = value.as_f64()?;
+                    let converted = number as $ty;
+
+                    (f64::from(converted) == number).then_some(converted)
+                });
+            }
+        )*};
+    }
+
+    if crate::api::type_eq::<T, JsValue>() {
+        return Ok(crate::cast_identity(value).unwrap());
+    }
+
+    if crate::api::type_eq::<T, Uint8Array>() {
+        let array = value.dyn_into::<Uint8Array>()?;
+
+        return Ok(crate::cast_identity(array).unwrap());
+    }
+
+    if crate::api::type_eq::<T, String>() {
+        return convert::<T, _>(value, JsValue::as_string);
+    }
+
+    if crate::api::type_eq::<T, f64>() {
+        return convert::<T, _>(value, JsValue::as_f64);
+    }
+
+    if crate::api::type_eq::<T, f32>() {
+        return convert::<T, _>(value, |value| {
+            let number = value.as_f64()?;
+            let converted = number as f32;
+
+            (f64::from(converted) == number || number.is_nan())
+                .then_some(converted)
+        });
+    }
+
+    number!(value; i32, u32, i16, u16, i8, u8);
+
+    if crate::api::type_eq::<T, bool>() {
+        return convert::<T, _>(value, JsValue::as_bool);
+    }
+
+    if crate::api::type_eq::<T, Vec<u8>>() {
+        return convert::<T, _>(value, |value| {
+            Some(value.dyn_ref::<Uint8Array>()?.to_vec())
+        });
+    }
+
+    Err(value)
+}
@@ -0,0 +1,15 @@
+//! Compile-fail tests pinning down which casts and specializers are rejected
+//!
+//! Diagnostics differ between compiler releases, so the expected output is
+//! only checked on the pinned stable toolchain.  Regenerate it with
+//! `TRYBUILD=overwrite cargo +1.95 test --test compile_fail` after an intended
+//! change.
+
+#[rustversion::attr(
+    not(stable(1.95)),
+    ignore = "diagnostics are only pinned for stable 1.95"
+)]
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,181 @@
+//! `try_run()` after converting between specializer types
+//!
+//! Arms added before a conversion still specialize on the parameter type, so
+//! the parameter is only handed back if no arm before or after the conversion
+//! specializes on it.
+
+use pasts::Executor;
+use specializer::{
+    AsyncSpecializer, AsyncSpecializerBorrowedParam,
+    AsyncSpecializerBorrowedReturn, Specializer, SpecializerBorrowedParam,
+    SpecializerBorrowedReturn,
+};
+
+static BYTES: [u8; 3] = [1, 2, 3];
+
+type Lookup = Option<&'static u8>;
+
+#[test]
+fn specializer_into_borrowed_param() {
+    fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        Specializer::new(ty, |_| unreachable!())
+            .specialize_param(|(): &()| None)
+            .into_borrowed_param()
+            .specialize_param(|index: &usize| BYTES.get(*index))
+            .try_run()
+    }
+
+    assert_eq!(lookup(&()), Ok(None));
+    assert_eq!(lookup(&1usize), Ok(Some(&2)));
+    assert_eq!(lookup(&1u8), Err(&1));
+}
+
+#[test]
+fn specializer_into_borrowed_return() {
+    fn lookup<T: 'static>(ty: T) -> Result<Lookup, T> {
+        Specializer::new(ty, |_| unreachable!())
+            .specialize_param(|(): ()| None)
+            .into_borrowed_return()
+            .specialize_param(|index: usize| BYTES.get(index))
+            .try_run()
+    }
+
+    assert_eq!(lookup(()), Ok(None));
+    assert_eq!(lookup(1usize), Ok(Some(&2)));
+    assert_eq!(lookup(1u8), Err(1));
+}
+
+#[test]
+fn specializer_into_borrowed() {
+    fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        Specializer::new(ty, |_| unreachable!())
+            .specialize_param(|(): &()| None)
+            .into_borrowed()
+            .specialize_param(|index: &usize| BYTES.get(*index))
+            .try_run()
+    }
+
+    assert_eq!(lookup(&()), Ok(None));
+    assert_eq!(lookup(&1usize), Ok(Some(&2)));
+    assert_eq!(lookup(&1u8), Err(&1));
+}
+
+#[test]
+fn borrowed_param_into_borrowed() {
+    fn lookup<T: 'static>(ty: &T) -> Result<Lookup, &T> {
+        SpecializerBorrowedParam::new(ty, |_| unreachable!())
+            .specialize_param(|(): &()| None)
+            .into_borrowed()
+            .specialize_param(|index: &usize| BYTES.get(*index))
+            .try_run()
+    }
+
+    assert_eq!(lookup(&()), Ok(None));
+    assert_eq!(lookup(&1usize), Ok(Some(&2)));
+    assert_eq!(lookup(&1u8), Err(&1));
+}
+
+#[test]
+fn borrowed_return_into_borrowed() {
+    fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        SpecializerBorrowedReturn::new(ty, |_| unreachable!())
+            .specialize_param(|(): &()| None)
+            .into_borrowed()
+            .specialize_param(|index: &usize| BYTES.get(*index))
+            .try_run()
+    }
+
+    assert_eq!(lookup(&()), Ok(None));
+    assert_eq!(lookup(&1usize), Ok(Some(&2)));
+    assert_eq!(lookup(&1u8), Err(&1));
+}
+
+#[test]
+fn async_specializer_into_borrowed_param() {
+    async fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        AsyncSpecializer::new(ty, async |_| unreachable!())
+            .specialize_param(async |(): &()| None)
+            .into_borrowed_param()
+            .specialize_param(async |index: &usize| BYTES.get(*index))
+            .try_run()
+            .await
+    }
+
+    Executor::default().block_on(async {
+        assert_eq!(lookup(&()).await, Ok(None));
+        assert_eq!(lookup(&1usize).await, Ok(Some(&2)));
+        assert_eq!(lookup(&1u8).await, Err(&1));
+    });
+}
+
+#[test]
+fn async_specializer_into_borrowed_return() {
+    async fn lookup<T: 'static>(ty: T) -> Result<Lookup, T> {
+        AsyncSpecializer::new(ty, async |_| unreachable!())
+            .specialize_param(async |(): ()| None)
+            .into_borrowed_return()
+            .specialize_param(async |index: usize| BYTES.get(index))
+            .try_run()
+            .await
+    }
+
+    Executor::default().block_on(async {
+        assert_eq!(lookup(()).await, Ok(None));
+        assert_eq!(lookup(1usize).await, Ok(Some(&2)));
+        assert_eq!(lookup(1u8).await, Err(1));
+    });
+}
+
+#[test]
+fn async_specializer_into_borrowed() {
+    async fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        AsyncSpecializer::new(ty, async |_| unreachable!())
+            .specialize_param(async |(): &()| None)
+            .into_borrowed()
+            .specialize_param(async |index: &usize| BYTES.get(*index))
+            .try_run()
+            .await
+    }
+
+    Executor::default().block_on(async {
+        assert_eq!(lookup(&()).await, Ok(None));
+        assert_eq!(lookup(&1usize).await, Ok(Some(&2)));
+        assert_eq!(lookup(&1u8).await, Err(&1));
+    });
+}
+
+#[test]
+fn async_borrowed_param_into_borrowed() {
+    async fn lookup<T: 'static>(ty: &T) -> Result<Lookup, &T> {
+        AsyncSpecializerBorrowedParam::new(ty, async |_| unreachable!())
+            .specialize_param(async |(): &()| None)
+            .into_borrowed()
+            .specialize_param(async |index: &usize| BYTES.get(*index))
+            .try_run()
+            .await
+    }
+
+    Executor::default().block_on(async {
+        assert_eq!(lookup(&()).await, Ok(None));
+        assert_eq!(lookup(&1usize).await, Ok(Some(&2)));
+        assert_eq!(lookup(&1u8).await, Err(&1));
+    });
+}
+
+#[test]
+fn async_borrowed_return_into_borrowed() {
+    async fn lookup<T: 'static>(ty: &'static T) -> Result<Lookup, &'static T> {
+        AsyncSpecializerBorrowedReturn::new(ty, async |_| unreachable!())
+            .specialize_param(async |(): &()| None)
+            .into_borrowed()
+            .specialize_param(async |index: &usize| BYTES.get(*index))
+            .try_run()
+            .await
+    }
+
+    Executor::default().block_on(async {
+        assert_eq!(lookup(&()).await, Ok(None));
+        assert_eq!(lookup(&1usize).await, Ok(Some(&2)));
+        assert_eq!(lookup(&1u8).await, Err(&1));
+    });
+}
@@ -0,0 +1,172 @@
+//! IO fast paths fall back to generic readers and writers, retrying
+//! interrupted operations
+
+#![cfg(feature = "std")]
+
+use std::io::{self, Read, Write};
+#[cfg(feature = "futures-io")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Reader and writer failing with `ErrorKind::Interrupted` every other call
+#[derive(Default)]
+struct Flaky {
+    data: Vec<u8>,
+    interrupt: bool,
+}
+
+impl Flaky {
+    fn new(data: &[u8]) -> Self {
+        Self {
+            data: data.to_vec(),
+            interrupt: false,
+        }
+    }
+
+    fn interrupted(&mut self) -> bool {
+        self.interrupt = !self.interrupt;
+        self.interrupt
+    }
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.interrupted() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        let len = buf.len().min(self.data.len()).min(2);
+
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data.drain(..len);
+        Ok(len)
+    }
+}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.interrupted() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        let len = buf.len().min(2);
+
+        self.data.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl futures_io::AsyncRead for Flaky {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().read(buf))
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl futures_io::AsyncWrite for Flaky {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().write(buf))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn read_to_end_retries() {
+    let mut buf = Vec::new();
+    let len =
+        specializer::read_to_end_fast(&mut Flaky::new(b"Hello"), &mut buf);
+
+    assert_eq!(len.unwrap(), 5);
+    assert_eq!(buf, b"Hello");
+}
+
+#[test]
+fn copy_retries() {
+    let mut reader: &[u8] = b"Hello";
+    let mut writer = Flaky::default();
+
+    assert_eq!(specializer::copy_fast(&mut reader, &mut writer).unwrap(), 5);
+    assert_eq!(writer.data, b"Hello");
+
+    let mut writer = Vec::new();
+
+    assert_eq!(
+        specializer::copy_fast(&mut Flaky::new(b"Hello"), &mut writer).unwrap(),
+        5,
+    );
+    assert_eq!(writer, b"Hello");
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn async_read_to_end_retries() {
+    pasts::Executor::default().block_on(async {
+        let mut buf = Vec::new();
+        let mut reader = Flaky::new(b"Hello");
+        let len = specializer::read_to_end_fast_async(&mut reader, &mut buf);
+
+        assert_eq!(len.await.unwrap(), 5);
+        assert_eq!(buf, b"Hello");
+    });
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn async_copy_retries() {
+    pasts::Executor::default().block_on(async {
+        let mut reader: &[u8] = b"Hello";
+        let mut writer = Flaky::default();
+        let len = specializer::copy_fast_async(&mut reader, &mut writer);
+
+        assert_eq!(len.await.unwrap(), 5);
+        assert_eq!(writer.data, b"Hello");
+
+        let mut reader = Flaky::new(b"Hello");
+        let mut writer = Flaky::default();
+        let len = specializer::copy_fast_async(&mut reader, &mut writer);
+
+        assert_eq!(len.await.unwrap(), 5);
+        assert_eq!(writer.data, b"Hello");
+    });
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn async_write_vectored_retries() {
+    pasts::Executor::default().block_on(async {
+        let mut writer = Flaky::default();
+        let bufs = [io::IoSlice::new(b"Hello")];
+        let len = specializer::write_vectored_fast_async(&mut writer, &bufs);
+
+        assert_eq!(len.await.unwrap(), 2);
+        assert_eq!(writer.data, b"He");
+    });
+}
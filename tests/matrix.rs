@@ -0,0 +1,409 @@
+//! Matrix of every combinator on every specializer type
+//!
+//! Each specializer is instantiated with representative parameter and return
+//! shapes, and each combinator is checked with a matching parameter and
+//! return, a mismatched parameter, and a mismatched return, so that a
+//! variant falling out of parity with the others fails here.
+
+use std::fmt::Debug;
+
+use pasts::Executor;
+use specializer::{
+    AsyncSpecializer, AsyncSpecializerBorrowed, AsyncSpecializerBorrowedParam,
+    AsyncSpecializerBorrowedReturn, Specializer, SpecializerBorrowed,
+    SpecializerBorrowedParam, SpecializerBorrowedReturn,
+};
+
+/// Small integer types that can be leaked as `&'static` references
+trait Num: Copy + Debug + PartialEq + Unpin + 'static {
+    fn new(n: u8) -> Self;
+    fn get(self) -> u8;
+    fn leak(n: u8) -> &'static Self;
+}
+
+macro_rules! num {
+    ($($ty:ty),*) => {$(
+        impl Num for $ty {
+            fn new(n: u8) -> Self {
+                n.into()
+            }
+
+            fn get(self) -> u8 {
+                self.try_into().unwrap()
+            }
+
+            fn leak(n: u8) -> &'static Self {
+                static TABLE: [$ty; 256] = {
+                    let mut table = [0; 256];
+                    let mut i = 0;
+
+                    while i < table.len() {
+                        table[i] = i as $ty;
+                        i += 1;
+                    }
+
+                    table
+                };
+
+                &TABLE[usize::from(n)]
+            }
+        }
+    )*};
+}
+
+num!(u16, u32);
+
+/// Parameter and return shapes
+///
+/// Each shape has `new()` to make a parameter from a local, `from_num()` to
+/// make a return value, `get()` to read either back, and `remap()` to add to
+/// either without changing its lifetime.
+mod shape {
+    use std::pin::Pin;
+
+    use super::Num;
+
+    pub mod owned {
+        use super::*;
+
+        pub type Of<'a, T> = T;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            *x
+        }
+
+        pub fn from_num<T: Num>(n: u8) -> Of<'static, T> {
+            T::new(n)
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.get()
+        }
+
+        pub fn remap<T: Num>(of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            T::new(of.get() + delta)
+        }
+    }
+
+    pub mod shared {
+        use super::*;
+
+        pub type Of<'a, T> = &'a T;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            x
+        }
+
+        pub fn from_num<T: Num>(n: u8) -> Of<'static, T> {
+            T::leak(n)
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.get()
+        }
+
+        pub fn remap<T: Num>(of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            T::leak(of.get() + delta)
+        }
+    }
+
+    pub mod unique {
+        use super::*;
+
+        pub type Of<'a, T> = &'a mut T;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            x
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.get()
+        }
+
+        pub fn remap<T: Num>(of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            *of = T::new(of.get() + delta);
+            of
+        }
+    }
+
+    pub mod pinned {
+        use super::*;
+
+        pub type Of<'a, T> = Pin<&'a T>;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            Pin::new(x)
+        }
+
+        pub fn from_num<T: Num>(n: u8) -> Of<'static, T> {
+            Pin::new(T::leak(n))
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.get()
+        }
+
+        pub fn remap<T: Num>(of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            Pin::new(T::leak(of.get() + delta))
+        }
+    }
+
+    pub mod pinned_mut {
+        use super::*;
+
+        pub type Of<'a, T> = Pin<&'a mut T>;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            Pin::new(x)
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.get()
+        }
+
+        pub fn remap<T: Num>(mut of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            *of = T::new(of.get() + delta);
+            of
+        }
+    }
+
+    pub mod optional {
+        use super::*;
+
+        pub type Of<'a, T> = Option<&'a T>;
+
+        pub fn new<T: Num>(x: &mut T) -> Of<'_, T> {
+            Some(x)
+        }
+
+        pub fn from_num<T: Num>(n: u8) -> Of<'static, T> {
+            Some(T::leak(n))
+        }
+
+        pub fn get<T: Num>(of: Of<'_, T>) -> u8 {
+            of.unwrap().get()
+        }
+
+        pub fn remap<T: Num>(of: Of<'_, T>, delta: u8) -> Of<'_, T> {
+            Some(T::leak(get(of) + delta))
+        }
+    }
+}
+
+/// Expand differently for synchronous and asynchronous specializers.
+macro_rules! mode {
+    (sync, await $e:expr) => { $e };
+    (async, await $e:expr) => { $e.await };
+    (sync, block $b:block) => { $b };
+    (async, block $b:block) => {
+        Executor::default().block_on(async move $b)
+    };
+    (sync, $($t:tt)*) => { $($t)* };
+    (async, $($t:tt)*) => { async $($t)* };
+}
+
+/// Dispatch with `$body`, an arm on `$specializer`, on a parameter of `T` and
+/// return of `U`, and check the result for each combination of types.
+macro_rules! case {
+    (
+        $mode:ident $specializer:ident<$param:ident, $ret:ident>,
+        $name:ident($s:ident) => $body:expr,
+        matched = $matched:expr,
+        param_only = $param_only:expr,
+        return_only = $return_only:expr $(,)?
+    ) => {
+        #[test]
+        fn $name() {
+            mode!($mode, fn dispatch<'a, T: Num, U: Num>(
+                param: shape::$param::Of<'a, T>,
+            ) -> shape::$ret::Of<'a, U> {
+                let $s = $specializer::new(
+                    param,
+                    mode!($mode, |p: shape::$param::Of<'a, T>| {
+                        shape::$ret::from_num::<U>(shape::$param::get(p))
+                    }),
+                );
+
+                mode!($mode, await $body.run())
+            });
+
+            mode!($mode, block {
+                let (mut x, mut y, mut z) = (3u32, 3u16, 3u32);
+                let out = mode!($mode, await dispatch::<u32, u32>(
+                    shape::$param::new(&mut x),
+                ));
+                assert_eq!(shape::$ret::get(out), $matched, "matched");
+
+                let out = mode!($mode, await dispatch::<u16, u32>(
+                    shape::$param::new(&mut y),
+                ));
+                assert_eq!(
+                    shape::$ret::get(out),
+                    $return_only,
+                    "only return matched",
+                );
+
+                let out = mode!($mode, await dispatch::<u32, u16>(
+                    shape::$param::new(&mut z),
+                ));
+                assert_eq!(
+                    shape::$ret::get(out),
+                    $param_only,
+                    "only parameter matched",
+                );
+            });
+        }
+    };
+}
+
+/// Check every combinator on `$specializer` with the given shapes.
+macro_rules! matrix {
+    ($(
+        $module:ident: $mode:ident $specializer:ident<$param:ident, $ret:ident>;
+    )*) => {$(
+        mod $module {
+            use super::*;
+
+            type Param<'a, T> = shape::$param::Of<'a, T>;
+            type Return<'a, T> = shape::$ret::Of<'a, T>;
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize(s) => s.specialize(
+                    mode!($mode, |p: Param<'a, u32>| -> Return<'a, u32> {
+                        shape::$ret::from_num(shape::$param::get(p) + 10)
+                    }),
+                ),
+                matched = 13,
+                param_only = 3,
+                return_only = 3,
+            }
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize_param(s) => s.specialize_param(
+                    mode!($mode, |p: Param<'a, u32>| -> Return<'a, U> {
+                        shape::$ret::from_num(shape::$param::get(p) + 20)
+                    }),
+                ),
+                matched = 23,
+                param_only = 23,
+                return_only = 3,
+            }
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize_return(s) => s.specialize_return(
+                    mode!($mode, |p: Param<'a, T>| -> Return<'a, u32> {
+                        shape::$ret::from_num(shape::$param::get(p) + 30)
+                    }),
+                ),
+                matched = 33,
+                param_only = 3,
+                return_only = 33,
+            }
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize_map(s) => s.specialize_map(
+                    mode!($mode, |p: Param<'a, u32>| {
+                        shape::$param::remap(p, 1)
+                    }),
+                    mode!($mode, |p: Param<'a, T>| -> Return<'a, U> {
+                        shape::$ret::from_num(shape::$param::get(p) + 40)
+                    }),
+                    mode!($mode, |r: Return<'a, u32>| {
+                        shape::$ret::remap(r, 2)
+                    }),
+                ),
+                matched = 46,
+                param_only = 3,
+                return_only = 3,
+            }
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize_map_param(s) => s.specialize_map_param(
+                    mode!($mode, |p: Param<'a, u32>| {
+                        shape::$param::remap(p, 1)
+                    }),
+                    mode!($mode, |p: Param<'a, T>| -> Return<'a, U> {
+                        shape::$ret::from_num(shape::$param::get(p) + 50)
+                    }),
+                ),
+                matched = 54,
+                param_only = 54,
+                return_only = 3,
+            }
+
+            case! {
+                $mode $specializer<$param, $ret>,
+                specialize_map_return(s) => s.specialize_map_return(
+                    mode!($mode, |p: Param<'a, T>| -> Return<'a, U> {
+                        shape::$ret::from_num(shape::$param::get(p) + 60)
+                    }),
+                    mode!($mode, |r: Return<'a, u32>| {
+                        shape::$ret::remap(r, 2)
+                    }),
+                ),
+                matched = 65,
+                param_only = 3,
+                return_only = 65,
+            }
+
+            // Arms added later are consulted first
+            case! {
+                $mode $specializer<$param, $ret>,
+                latest_arm_wins(s) => s
+                    .specialize_param(
+                        mode!($mode, |p: Param<'a, u16>| -> Return<'a, U> {
+                            shape::$ret::from_num(shape::$param::get(p) + 70)
+                        }),
+                    )
+                    .specialize_return(
+                        mode!($mode, |p: Param<'a, T>| -> Return<'a, u32> {
+                            shape::$ret::from_num(shape::$param::get(p) + 80)
+                        }),
+                    )
+                    .specialize_param(
+                        mode!($mode, |p: Param<'a, u32>| -> Return<'a, U> {
+                            shape::$ret::from_num(shape::$param::get(p) + 90)
+                        }),
+                    ),
+                matched = 93,
+                param_only = 93,
+                return_only = 83,
+            }
+        }
+    )*};
+}
+
+matrix! {
+    owned: sync Specializer<owned, owned>;
+    borrowed_unique: sync SpecializerBorrowed<unique, optional>;
+    borrowed_pinned_mut: sync SpecializerBorrowed<pinned_mut, shared>;
+    borrowed_pinned: sync SpecializerBorrowed<pinned, pinned>;
+    borrowed_param_shared: sync SpecializerBorrowedParam<shared, owned>;
+    borrowed_param_unique: sync SpecializerBorrowedParam<unique, owned>;
+    borrowed_param_optional: sync SpecializerBorrowedParam<optional, owned>;
+    borrowed_return_shared: sync SpecializerBorrowedReturn<owned, shared>;
+    borrowed_return_pinned: sync SpecializerBorrowedReturn<owned, pinned>;
+    borrowed_return_optional: sync SpecializerBorrowedReturn<owned, optional>;
+    async_owned: async AsyncSpecializer<owned, owned>;
+    async_borrowed_unique: async AsyncSpecializerBorrowed<unique, optional>;
+    async_borrowed_pinned_mut:
+        async AsyncSpecializerBorrowed<pinned_mut, shared>;
+    async_borrowed_pinned: async AsyncSpecializerBorrowed<pinned, pinned>;
+    async_borrowed_param_shared:
+        async AsyncSpecializerBorrowedParam<shared, owned>;
+    async_borrowed_param_unique:
+        async AsyncSpecializerBorrowedParam<unique, owned>;
+    async_borrowed_param_optional:
+        async AsyncSpecializerBorrowedParam<optional, owned>;
+    async_borrowed_return_shared:
+        async AsyncSpecializerBorrowedReturn<owned, shared>;
+    async_borrowed_return_pinned:
+        async AsyncSpecializerBorrowedReturn<owned, pinned>;
+    async_borrowed_return_optional:
+        async AsyncSpecializerBorrowedReturn<owned, optional>;
+}
@@ -0,0 +1,50 @@
+//! Arms recorded by the `test-util` probe
+
+#![cfg(feature = "test-util")]
+
+use specializer::{SpecializeProbe, Specializer, assert_specializes};
+
+fn describe<T: 'static>(ty: T) -> String {
+    Specializer::new(ty, |_| "other".to_owned())
+        .specialize_filter(
+            |int: &i32| *int > 0,
+            |int| -> String { format!("positive int {int}") },
+        )
+        .run()
+}
+
+#[test]
+fn declined_arm_is_missed() {
+    let probe = SpecializeProbe::new();
+
+    assert_eq!(describe(-3i32), "other");
+    assert_eq!(probe.consulted(), ["i32"]);
+    assert!(probe.matched().is_empty());
+
+    assert_specializes!(describe(-3i32), fallback);
+    assert_specializes!(describe(3i32), arm = "i32");
+}
+
+#[test]
+fn erased_dispatch_is_named() {
+    let mut spec = specializer::DynSpecializer::new();
+    let probe = SpecializeProbe::new();
+
+    spec.register(|int: i32| int * 2);
+
+    assert_eq!(spec.dispatch_boxed(Box::new(3i32)).ok(), Some(6));
+    assert!(spec.dispatch_boxed(Box::new(3u8)).is_err());
+    assert_eq!(probe.consulted(), ["i32", "<unknown type>"]);
+    assert_eq!(probe.matched(), ["i32"]);
+}
+
+#[test]
+fn clear_after_outer_probe_dropped() {
+    let outer = SpecializeProbe::new();
+    let inner = SpecializeProbe::new();
+
+    drop(outer);
+    inner.clear();
+
+    assert!(inner.consulted().is_empty());
+}
@@ -0,0 +1,184 @@
+//! Property tests for the structural `CastIdentityBorrowed` impls
+//!
+//! Runs fewer cases under Miri (`cargo +nightly miri test --test
+//! structural_casts`), which checks the casts for undefined behavior.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{pin::Pin, task::Poll};
+
+use proptest::{prelude::*, test_runner::Config};
+use specializer::{CastIdentityBorrowed, cast_identity_borrowed};
+
+fn config() -> Config {
+    Config {
+        cases: if cfg!(miri) { 4 } else { 256 },
+        failure_persistence: None,
+        ..Config::default()
+    }
+}
+
+/// Tuple of options and results of shared references
+type Refs<'a, T> =
+    (Option<&'a u32>, Option<&'a i64>, Result<&'a T, &'a String>);
+
+/// Assert that casting succeeds exactly when `is_same()` says it should.
+fn consistent<T, U>(value: T) -> Option<U>
+where
+    T: CastIdentityBorrowed<U>,
+{
+    let is_same = <T as CastIdentityBorrowed<U>>::is_same();
+    let cast = cast_identity_borrowed::<T, U>(value);
+
+    assert_eq!(cast.is_some(), is_same);
+    cast
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn nested_refs_round_trip(
+        int in any::<u32>(),
+        option in any::<Option<i64>>(),
+        result in any::<Result<u8, String>>(),
+    ) {
+        let value = (Some(&int), option.as_ref(), result.as_ref());
+        let cast = consistent::<_, Refs<'_, u8>>(value);
+
+        prop_assert_eq!(cast, Some(value));
+    }
+
+    #[test]
+    fn nested_refs_mismatch(
+        int in any::<u32>(),
+        option in any::<Option<i64>>(),
+        result in any::<Result<u8, String>>(),
+    ) {
+        let value = (Some(&int), option.as_ref(), result.as_ref());
+        let cast = consistent::<_, Refs<'_, u16>>(value);
+
+        prop_assert_eq!(cast, None);
+    }
+
+    #[test]
+    fn nested_mut_writes_through(
+        mut int in any::<u32>(),
+        mut option in any::<Option<i64>>(),
+        mut result in any::<Result<u8, String>>(),
+    ) {
+        let (old_int, old_option, old_result) =
+            (int, option, result.clone());
+        let value = (
+            Poll::Ready(&mut int),
+            (option.as_mut(), result.as_mut()),
+        );
+        let cast = consistent::<
+            _,
+            (Poll<&mut u32>, (Option<&mut i64>, Result<&mut u8, &mut String>)),
+        >(value);
+        let Some((Poll::Ready(int_mut), (option_mut, result_mut))) = cast
+        else {
+            panic!("identity cast failed");
+        };
+
+        *int_mut = int_mut.wrapping_add(1);
+        if let Some(option) = option_mut {
+            *option = option.wrapping_add(1);
+        }
+        match result_mut {
+            Ok(result) => *result = result.wrapping_add(1),
+            Err(string) => string.push('!'),
+        }
+
+        prop_assert_eq!(int, old_int.wrapping_add(1));
+        prop_assert_eq!(option, old_option.map(|x| x.wrapping_add(1)));
+        prop_assert_eq!(
+            result,
+            old_result
+                .map(|x| x.wrapping_add(1))
+                .map_err(|string| string + "!"),
+        );
+    }
+
+    #[test]
+    fn pinned_round_trip(int in any::<u64>(), string in any::<String>()) {
+        let value = (Pin::new(&int), Some(Pin::new(&string)));
+        let cast = consistent::<_, (Pin<&u64>, Option<Pin<&String>>)>(value);
+
+        prop_assert_eq!(cast, Some(value));
+        prop_assert_eq!(
+            consistent::<_, (Pin<&u32>, Option<Pin<&String>>)>(value),
+            None,
+        );
+    }
+
+    #[test]
+    fn unsized_refs_round_trip(
+        string in any::<String>(),
+        mut bytes in any::<Vec<u8>>(),
+    ) {
+        let value = (string.as_str(), Some(bytes.as_slice()));
+        let cast = consistent::<_, (&str, Option<&[u8]>)>(value);
+
+        prop_assert_eq!(cast, Some(value));
+        prop_assert_eq!(consistent::<_, (&str, Option<&[i8]>)>(value), None);
+        prop_assert_eq!(consistent::<_, (&[u8], Option<&[u8]>)>(value), None);
+
+        let len = bytes.len();
+        let cast = consistent::<_, &mut [u8]>(bytes.as_mut_slice());
+
+        prop_assert_eq!(cast.map(|bytes| bytes.len()), Some(len));
+        prop_assert!(consistent::<_, &mut str>(bytes.as_mut_slice()).is_none());
+    }
+
+    #[test]
+    fn result_of_options_mismatch_on_either_side(
+        result in any::<Result<Option<u8>, Option<i8>>>(),
+    ) {
+        let value = result.as_ref().map(Option::as_ref).map_err(Option::as_ref);
+
+        prop_assert_eq!(
+            consistent::<_, Result<Option<&u8>, Option<&i8>>>(value),
+            Some(value),
+        );
+        prop_assert_eq!(
+            consistent::<_, Result<Option<&u8>, Option<&u8>>>(value),
+            None,
+        );
+        prop_assert_eq!(
+            consistent::<_, Result<Option<&i8>, Option<&i8>>>(value),
+            None,
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn cows_round_trip(string in any::<String>(), bytes in any::<Vec<u8>>()) {
+        use std::borrow::Cow;
+
+        type Cows<'a, T> = (Cow<'a, str>, Cow<'a, [T]>);
+
+        let value = (Cow::Borrowed(string.as_str()), Cow::Owned(bytes));
+        let cast = consistent::<_, Cows<'_, u8>>(value.clone());
+
+        prop_assert_eq!(cast, Some(value.clone()));
+        prop_assert_eq!(consistent::<_, Cows<'_, i8>>(value), None);
+    }
+
+    #[test]
+    fn boxed_refs_round_trip(int in any::<u32>(), string in any::<String>()) {
+        let value = Box::new((&int, Some(string.as_str())));
+        let cast = consistent::<_, Box<(&u32, Option<&str>)>>(value.clone());
+
+        prop_assert_eq!(cast, Some(value.clone()));
+        prop_assert_eq!(
+            consistent::<_, Box<(&i32, Option<&str>)>>(value),
+            None,
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Dispatches recorded with the `log` feature
+
+#![cfg(all(feature = "log", feature = "alloc"))]
+
+use std::{
+    sync::Mutex,
+    thread::{self, ThreadId},
+};
+
+use pasts::Executor;
+use specializer::{
+    AnySpecializerRef, AsyncSpecializer, DynSpecializer, Specializer,
+    SpecializerMut,
+};
+
+/// Messages logged by `specializer`, with the thread that logged them
+static RECORDS: Mutex<Vec<(ThreadId, String)>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.target() == "specializer"
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let message = (thread::current().id(), record.args().to_string());
+
+            RECORDS.lock().unwrap().push(message);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Return the messages logged on this thread while running `f`.
+fn records(f: impl FnOnce()) -> Vec<String> {
+    let _ = log::set_logger(&Logger);
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let thread = thread::current().id();
+
+    RECORDS.lock().unwrap().retain(|(id, _)| *id != thread);
+    f();
+    RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(id, _)| *id == thread)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
+
+fn describe<T: 'static>(ty: T) -> String {
+    Specializer::new(ty, |_| "other".to_owned())
+        .specialize_filter(|int: &i32| *int > 0, |int| int.to_string())
+        .specialize_param(|string: String| string)
+        .run()
+}
+
+#[test]
+fn arm_or_fallback() {
+    assert_eq!(
+        records(|| assert_eq!(describe(3), "3")),
+        ["dispatch i32: arm"],
+    );
+    assert_eq!(
+        records(|| assert_eq!(describe("a".to_owned()), "a")),
+        ["dispatch alloc::string::String: arm"],
+    );
+    assert_eq!(
+        records(|| assert_eq!(describe(()), "other")),
+        ["dispatch (): fallback"],
+    );
+}
+
+#[test]
+fn declined_arm_runs_fallback() {
+    assert_eq!(
+        records(|| assert_eq!(describe(-3), "other")),
+        ["dispatch i32: fallback"],
+    );
+}
+
+#[test]
+fn without_arms() {
+    let run = || assert_eq!(Specializer::new(3, |int| int * 2).run(), 6);
+
+    assert_eq!(records(run), ["dispatch i32: fallback"]);
+}
+
+#[test]
+fn borrowed() {
+    fn len<T: 'static>(ty: &T) -> usize {
+        Specializer::by_ref(ty, |_| 0)
+            .specialize_param(|string: &String| string.len())
+            .run()
+    }
+
+    assert_eq!(
+        records(|| assert_eq!(len(&"ab".to_owned()), 2)),
+        ["dispatch &alloc::string::String: arm"],
+    );
+    assert_eq!(
+        records(|| assert_eq!(len(&()), 0)),
+        ["dispatch &(): fallback"],
+    );
+}
+
+#[test]
+fn mutable() {
+    let mut spec = SpecializerMut::new(|_| "other")
+        .specialize_param(|_: i32| "int")
+        .specialize_param(|_: u8| "byte");
+
+    assert_eq!(
+        records(|| assert_eq!(spec.run_with(3u8), "byte")),
+        ["dispatch u8: arm"],
+    );
+}
+
+#[test]
+fn asynchronous() {
+    async fn describe<T: 'static>(ty: T) -> &'static str {
+        AsyncSpecializer::new(ty, async |_| "other")
+            .specialize_param(async |_: i32| "int")
+            .run()
+            .await
+    }
+
+    fn run(future: impl Future<Output = ()> + 'static) {
+        Executor::default().block_on(future);
+    }
+
+    assert_eq!(
+        records(|| run(async { assert_eq!(describe(3).await, "int") })),
+        ["dispatch i32: arm"],
+    );
+    assert_eq!(
+        records(|| run(async { assert_eq!(describe(()).await, "other") })),
+        ["dispatch (): fallback"],
+    );
+}
+
+#[test]
+fn type_erased() {
+    let describe = |any| {
+        AnySpecializerRef::new(any, |_| "other")
+            .specialize_param(|_: &i32| "int")
+            .run()
+    };
+
+    assert_eq!(
+        records(|| assert_eq!(describe(&3), "int")),
+        ["dispatch i32: arm"],
+    );
+    assert_eq!(
+        records(|| assert_eq!(describe(&()), "other")),
+        ["dispatch dyn core::any::Any: fallback"],
+    );
+}
+
+#[test]
+fn cache_hit() {
+    let mut spec = DynSpecializer::new();
+
+    spec.register(|int: i32| int * 2);
+
+    let cached = spec.cached();
+    let run = || {
+        assert_eq!(cached.dispatch(3), Ok(6));
+        assert_eq!(cached.dispatch(4), Ok(8));
+    };
+
+    assert_eq!(records(run), ["dispatch i32: arm", "dispatch i32: arm"]);
+}
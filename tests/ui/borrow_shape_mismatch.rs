@@ -0,0 +1,12 @@
+// An arm's parameter must have a borrow shape the specializer's parameter
+// can be cast to.
+
+use specializer::SpecializerBorrowedParam;
+
+fn main() {
+    let mut int = 1u8;
+
+    SpecializerBorrowedParam::new(&mut int, |int| usize::from(*int))
+        .specialize_param(|string: String| string.len())
+        .run();
+}
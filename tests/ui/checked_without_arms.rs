@@ -0,0 +1,11 @@
+// `run()` is only available once a `CheckedSpecializer` has at least one arm.
+
+use specializer::CheckedSpecializer;
+
+fn describe<T: 'static>(ty: T) -> String {
+    CheckedSpecializer::new(ty, |_| "unknown".to_owned()).run()
+}
+
+fn main() {
+    describe(3);
+}
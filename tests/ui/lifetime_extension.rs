@@ -0,0 +1,11 @@
+// Casting a reference can't extend its lifetime.
+
+fn extend<'a>(int: &'a u8) -> Option<&'static u8> {
+    specializer::cast_identity_borrowed(int)
+}
+
+fn main() {
+    let int = 1u8;
+
+    extend(&int);
+}
@@ -0,0 +1,12 @@
+// Owned casts require `'static` types, since `TypeId` can't tell lifetimes
+// apart.
+
+fn cast<'a>(string: &'a str) -> Option<&'a str> {
+    specializer::cast_identity::<&'a str, &'a str>(string)
+}
+
+fn main() {
+    let string = String::from("Hello");
+
+    cast(&string);
+}
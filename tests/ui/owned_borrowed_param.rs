@@ -0,0 +1,7 @@
+// The borrowed specializers need a borrowed parameter shape.
+
+use specializer::SpecializerBorrowedParam;
+
+fn main() {
+    SpecializerBorrowedParam::new(String::new(), |string| string.len()).run();
+}
@@ -0,0 +1,8 @@
+// Tuples of different arities have no cross impls, so casting between them
+// is rejected instead of always failing at runtime.
+
+fn main() {
+    let int = 1u8;
+
+    specializer::cast_identity_borrowed::<(&u8,), (&u8, &u8)>((&int,));
+}
@@ -0,0 +1,57 @@
+//! Fast paths between Rust values and JavaScript values
+//!
+//! Only built for WebAssembly.  Run with `wasm-bindgen-test-runner` (from
+//! `wasm-bindgen-cli`) as the runner:
+//!
+//! ```sh
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test --target wasm32-unknown-unknown --features wasm-bindgen \
+//!     --test wasm_fast
+//! ```
+
+#![cfg(all(feature = "wasm-bindgen", target_arch = "wasm32"))]
+
+use specializer::{from_js_value_fast, into_js_value_fast};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn round_trip<T: 'static>(value: T) -> Result<T, JsValue> {
+    from_js_value_fast(into_js_value_fast(value, |_| unreachable!()))
+}
+
+#[wasm_bindgen_test]
+fn numbers_round_trip() {
+    assert_eq!(round_trip(1.5f64).ok(), Some(1.5));
+    assert_eq!(round_trip(1.5f32).ok(), Some(1.5));
+    assert_eq!(round_trip(i32::MIN).ok(), Some(i32::MIN));
+    assert_eq!(round_trip(u32::MAX).ok(), Some(u32::MAX));
+    assert_eq!(round_trip(-3i16).ok(), Some(-3));
+    assert_eq!(round_trip(3u16).ok(), Some(3));
+    assert_eq!(round_trip(-3i8).ok(), Some(-3));
+    assert_eq!(round_trip(3u8).ok(), Some(3));
+    assert!(round_trip(f32::NAN).unwrap().is_nan());
+}
+
+#[wasm_bindgen_test]
+fn numbers_must_be_exact() {
+    assert!(from_js_value_fast::<u8>(JsValue::from_f64(256.0)).is_err());
+    assert!(from_js_value_fast::<u8>(JsValue::from_f64(-1.0)).is_err());
+    assert!(from_js_value_fast::<i32>(JsValue::from_f64(1.5)).is_err());
+    assert!(from_js_value_fast::<u32>(JsValue::from_f64(f64::NAN)).is_err());
+    assert!(from_js_value_fast::<f32>(JsValue::from_f64(0.1)).is_err());
+    assert!(from_js_value_fast::<u8>(JsValue::from_str("3")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn other_types_round_trip() {
+    assert_eq!(
+        round_trip("Hello".to_owned()).ok().as_deref(),
+        Some("Hello")
+    );
+    assert_eq!(round_trip(true).ok(), Some(true));
+    assert_eq!(
+        round_trip([1u8, 2, 3].to_vec()).ok(),
+        Some([1, 2, 3].into())
+    );
+    assert!(from_js_value_fast::<()>(JsValue::NULL).is_err());
+}